@@ -0,0 +1,95 @@
+#!/usr/bin/env python3
+"""Generate the `feeds` registry of well-known Pyth price feed ids.
+
+The registry is derived from `feeds/hermes_price_feeds.json`, a snapshot of the `/v2/price_feeds` endpoint of Hermes
+restricted to the symbols in `SYMBOLS`. Solana mainnet and devnet both receive the stable Pyth price feeds served by
+`https://hermes.pyth.network`, so the same snapshot is used for both clusters.
+
+Usage:
+    python3 scripts/generate_feeds.py --fetch  # Refresh the snapshot from Hermes, then regenerate the registry
+    python3 scripts/generate_feeds.py          # Regenerate the registry from the checked-in snapshot
+
+Bump `feeds::VERSION` whenever the generated registry changes.
+"""
+
+import argparse
+import json
+import re
+import urllib.request
+from pathlib import Path
+
+HERMES_URL = "https://hermes.pyth.network/v2/price_feeds"
+ROOT = Path(__file__).resolve().parent.parent
+SNAPSHOT = ROOT / "feeds" / "hermes_price_feeds.json"
+GENERATED = ROOT / "src" / "feeds" / "generated.rs"
+
+# The feeds of the registry, in the order of `feeds::ALL`
+SYMBOLS = [
+    "Crypto.BTC/USD",
+    "Crypto.ETH/USD",
+    "Crypto.SOL/USD",
+    "Crypto.USDC/USD",
+    "Crypto.USDT/USD",
+    "Crypto.MSOL/USD",
+    "Crypto.JITOSOL/USD",
+    "Crypto.BONK/USD",
+    "Crypto.JUP/USD",
+    "Crypto.PYTH/USD",
+]
+ATTRIBUTES = ["asset_type", "base", "quote_currency", "symbol"]
+
+
+def fetch_snapshot():
+    with urllib.request.urlopen(HERMES_URL) as response:
+        price_feeds = json.load(response)
+    by_symbol = {feed["attributes"].get("symbol"): feed for feed in price_feeds}
+    missing = [symbol for symbol in SYMBOLS if symbol not in by_symbol]
+    if missing:
+        raise SystemExit(f"Hermes doesn't list {', '.join(missing)}")
+    snapshot = [
+        {
+            "id": by_symbol[symbol]["id"],
+            "attributes": {key: by_symbol[symbol]["attributes"][key] for key in ATTRIBUTES},
+        }
+        for symbol in SYMBOLS
+    ]
+    SNAPSHOT.write_text(json.dumps(snapshot, indent=2) + "\n")
+
+
+def rust_identifier(name):
+    identifier = re.sub(r"[^A-Za-z0-9]", "_", name).upper()
+    return f"_{identifier}" if identifier[0].isdigit() else identifier
+
+
+def generate():
+    snapshot = json.loads(SNAPSHOT.read_text())
+    modules = {}
+    entries = []
+    for feed in snapshot:
+        attributes = feed["attributes"]
+        module = rust_identifier(attributes["asset_type"]).lower()
+        constant = f'{rust_identifier(attributes["base"])}_{rust_identifier(attributes["quote_currency"])}'
+        modules.setdefault(module, []).append((constant, feed["id"]))
+        entries.append((attributes["symbol"], f"{module}::{constant}"))
+
+    lines = [
+        "// @generated by scripts/generate_feeds.py from feeds/hermes_price_feeds.json, do not edit.",
+        "use crate::feed_id::FeedId;",
+    ]
+    for module, constants in modules.items():
+        lines += ["", f"pub mod {module} {{", "    use crate::{", "        feed_id,", "        feed_id::FeedId,", "    };", ""]
+        for constant, feed_id in constants:
+            lines += [f"    pub const {constant}: FeedId =", f'        feed_id!("0x{feed_id}");']
+        lines.append("}")
+    lines += ["", "/// All the feeds in this registry, as pairs of Pyth symbol and `FeedId`.", "pub const ALL: &[(&str, FeedId)] = &["]
+    lines += [f'    ("{symbol}", {path}),' for symbol, path in entries]
+    lines.append("];")
+    GENERATED.write_text("\n".join(lines) + "\n")
+
+
+if __name__ == "__main__":
+    parser = argparse.ArgumentParser(description=__doc__, formatter_class=argparse.RawDescriptionHelpFormatter)
+    parser.add_argument("--fetch", action="store_true", help="refresh the snapshot from Hermes first")
+    if parser.parse_args().fetch:
+        fetch_snapshot()
+    generate()
@@ -0,0 +1,401 @@
+//! Parsing of the accumulator updates served by Hermes, for off-chain clients that post price updates.
+//!
+//! An accumulator update, identified by its `PNAU` magic, contains a Wormhole VAA signing the Merkle root of a set of price messages,
+//! and some of these messages with their Merkle proofs. Each message can then be posted with the `post_update` instructions of the [`client`](crate::client) module.
+use {
+    crate::{
+        client::MerklePriceUpdate,
+        price_update::PriceFeedMessage,
+    },
+    pythnet_sdk::{
+        accumulators::merkle::{
+            MerklePath,
+            MerkleRoot,
+        },
+        hashers::keccak256_160::Keccak160,
+        messages::Message,
+        wire::{
+            from_slice,
+            v1::{
+                AccumulatorUpdateData,
+                Proof,
+                WormholeMessage,
+                WormholePayload,
+                PYTHNET_ACCUMULATOR_UPDATE_MAGIC,
+            },
+        },
+    },
+    std::fmt,
+};
+
+const ACCUMULATOR_UPDATE_MAJOR_VERSION: u8 = 1;
+
+// Offsets of the fields of a Wormhole VAA
+const VAA_GUARDIAN_SET_INDEX_OFFSET: usize = 1;
+const VAA_NUM_SIGNATURES_OFFSET: usize = VAA_GUARDIAN_SET_INDEX_OFFSET + 4;
+const VAA_SIGNATURES_OFFSET: usize = VAA_NUM_SIGNATURES_OFFSET + 1;
+const VAA_SIGNATURE_LEN: usize = 66;
+// timestamp, nonce, emitter_chain, emitter_address, sequence and consistency_level
+const VAA_BODY_HEADER_LEN: usize = 4 + 4 + 2 + 32 + 8 + 1;
+
+/// The error returned when parsing an accumulator update fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccumulatorError {
+    /// The data doesn't start with the `PNAU` magic.
+    InvalidMagic,
+    /// The major version of the accumulator update isn't supported.
+    InvalidVersion,
+    /// The accumulator update or its VAA is truncated or malformed.
+    InvalidData,
+    /// The payload of the VAA isn't a Merkle root.
+    InvalidWormholeMessage,
+    /// The message verified by [`verify_price_feed_message`] isn't a price feed message.
+    UnsupportedMessage,
+    /// The Merkle proof of a message doesn't match the Merkle root.
+    InvalidMerkleProof,
+}
+
+impl fmt::Display for AccumulatorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            AccumulatorError::InvalidMagic => "The accumulator update doesn't start with PNAU",
+            AccumulatorError::InvalidVersion => "Unsupported accumulator update version",
+            AccumulatorError::InvalidData => {
+                "The accumulator update or its VAA is truncated or malformed"
+            }
+            AccumulatorError::InvalidWormholeMessage => "The VAA payload isn't a Merkle root",
+            AccumulatorError::UnsupportedMessage => "The message isn't a price feed message",
+            AccumulatorError::InvalidMerkleProof => {
+                "The Merkle proof of the message doesn't match the Merkle root"
+            }
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for AccumulatorError {}
+
+/// A decoded accumulator update.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccumulatorUpdate {
+    /// The Wormhole VAA signing `merkle_root`. It must be posted to an encoded VAA account, or included in a `post_update_atomic` instruction.
+    pub vaa:                Vec<u8>,
+    /// The index of the guardian set that signed the VAA, see [`get_guardian_set_address`](crate::pda::get_guardian_set_address).
+    pub guardian_set_index: u32,
+    /// The Pythnet slot of the Merkle root.
+    pub slot:               u64,
+    /// The Merkle root of the price messages, signed by the VAA.
+    pub merkle_root:        [u8; 20],
+    /// The price feed messages of the update. Other messages, such as TWAP messages, are skipped.
+    pub updates:            Vec<PriceFeedUpdate>,
+}
+
+/// A price feed message of an accumulator update, along with its Merkle proof.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceFeedUpdate {
+    /// The decoded price feed message.
+    pub message:             PriceFeedMessage,
+    /// The encoded message and its Merkle proof, as expected by the `post_update` instructions.
+    pub merkle_price_update: MerklePriceUpdate,
+}
+
+impl AccumulatorUpdate {
+    /// Decode an accumulator update, such as the binary data returned by Hermes for a price update request.
+    ///
+    /// This only decodes the data. The VAA's signatures and the Merkle proofs of the messages are not verified.
+    pub fn try_from_slice(data: &[u8]) -> std::result::Result<Self, AccumulatorError> {
+        if !data.starts_with(PYTHNET_ACCUMULATOR_UPDATE_MAGIC) {
+            return Err(AccumulatorError::InvalidMagic);
+        }
+        match data.get(PYTHNET_ACCUMULATOR_UPDATE_MAGIC.len()) {
+            Some(&ACCUMULATOR_UPDATE_MAJOR_VERSION) => {}
+            Some(_) => return Err(AccumulatorError::InvalidVersion),
+            None => return Err(AccumulatorError::InvalidData),
+        }
+        let accumulator_update = AccumulatorUpdateData::try_from_slice(data)
+            .map_err(|_| AccumulatorError::InvalidData)?;
+
+        let Proof::WormholeMerkle { vaa, updates } = accumulator_update.proof;
+        let vaa: Vec<u8> = vaa.into();
+        let (guardian_set_index, payload) = parse_vaa(&vaa)?;
+        let WormholePayload::Merkle(root) = WormholeMessage::try_from_bytes(payload)
+            .map_err(|_| AccumulatorError::InvalidWormholeMessage)?
+            .payload;
+
+        let updates = updates
+            .into_iter()
+            .filter_map(|update| {
+                let merkle_price_update = MerklePriceUpdate::from(update);
+                match decode_price_feed_message(&merkle_price_update.message) {
+                    Ok(message) => Some(Ok(PriceFeedUpdate {
+                        message,
+                        merkle_price_update,
+                    })),
+                    Err(AccumulatorError::UnsupportedMessage) => None,
+                    Err(err) => Some(Err(err)),
+                }
+            })
+            .collect::<std::result::Result<_, _>>()?;
+
+        Ok(AccumulatorUpdate {
+            vaa,
+            guardian_set_index,
+            slot: root.slot,
+            merkle_root: root.root,
+            updates,
+        })
+    }
+}
+
+/// Verify that the message of `merkle_price_update` is included in `merkle_root`, and decode it.
+///
+/// This is the same check as the one performed by the receiver program before posting a price update, so it can be used to avoid paying for an update that would fail.
+/// `merkle_root` should come from a VAA whose signatures have been verified, such as the [`AccumulatorUpdate::merkle_root`] of an update served by Hermes.
+///
+/// Returns `AccumulatorError::InvalidMerkleProof` if the proof doesn't match the root.
+pub fn verify_price_feed_message(
+    merkle_root: &[u8; 20],
+    merkle_price_update: &MerklePriceUpdate,
+) -> std::result::Result<PriceFeedMessage, AccumulatorError> {
+    let proof = MerklePath::<Keccak160>::new(merkle_price_update.proof.clone());
+    if !MerkleRoot::<Keccak160>::new(*merkle_root).check(proof, &merkle_price_update.message) {
+        return Err(AccumulatorError::InvalidMerkleProof);
+    }
+    decode_price_feed_message(&merkle_price_update.message)
+}
+
+fn decode_price_feed_message(
+    message: &[u8],
+) -> std::result::Result<PriceFeedMessage, AccumulatorError> {
+    match from_slice::<byteorder::BE, Message>(message)
+        .map_err(|_| AccumulatorError::InvalidData)?
+    {
+        Message::PriceFeedMessage(message) => Ok(message.into()),
+        _ => Err(AccumulatorError::UnsupportedMessage),
+    }
+}
+
+/// Get the guardian set index and the payload of a Wormhole VAA.
+fn parse_vaa(vaa: &[u8]) -> std::result::Result<(u32, &[u8]), AccumulatorError> {
+    let guardian_set_index = vaa
+        .get(VAA_GUARDIAN_SET_INDEX_OFFSET..VAA_NUM_SIGNATURES_OFFSET)
+        .ok_or(AccumulatorError::InvalidData)?;
+    let num_signatures = *vaa
+        .get(VAA_NUM_SIGNATURES_OFFSET)
+        .ok_or(AccumulatorError::InvalidData)?;
+    let payload_offset = VAA_SIGNATURES_OFFSET
+        + usize::from(num_signatures) * VAA_SIGNATURE_LEN
+        + VAA_BODY_HEADER_LEN;
+    let payload = vaa
+        .get(payload_offset..)
+        .ok_or(AccumulatorError::InvalidData)?;
+    Ok((
+        u32::from_be_bytes(guardian_set_index.try_into().unwrap()),
+        payload,
+    ))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use {
+        super::{
+            verify_price_feed_message,
+            AccumulatorError,
+            AccumulatorUpdate,
+        },
+        crate::price_update::PriceFeedMessage,
+        pythnet_sdk::{
+            accumulators::{
+                merkle::MerkleTree,
+                Accumulator,
+            },
+            hashers::keccak256_160::Keccak160,
+            messages::{
+                Message,
+                TwapMessage,
+            },
+            wire::{
+                to_vec,
+                v1::{
+                    AccumulatorUpdateData,
+                    MerklePriceUpdate,
+                    Proof,
+                    WormholeMerkleRoot,
+                    WormholeMessage,
+                    WormholePayload,
+                },
+            },
+        },
+    };
+
+    fn price_feed_message(feed_id: u8, price: i64) -> pythnet_sdk::messages::PriceFeedMessage {
+        pythnet_sdk::messages::PriceFeedMessage {
+            feed_id: [feed_id; 32],
+            price,
+            conf: 10,
+            exponent: -8,
+            publish_time: 100,
+            prev_publish_time: 99,
+            ema_price: price,
+            ema_conf: 10,
+        }
+    }
+
+    /// Build an accumulator update with a VAA from guardian set 4 signing the Merkle root of `messages`, and proofs for all the `messages`.
+    fn accumulator_update_data(messages: &[Message]) -> Vec<u8> {
+        let messages: Vec<Vec<u8>> = messages
+            .iter()
+            .map(|message| to_vec::<_, byteorder::BE>(message).unwrap())
+            .collect();
+        let tree = MerkleTree::<Keccak160>::from_set(messages.iter().map(Vec::as_slice)).unwrap();
+
+        let mut vaa = vec![1];
+        vaa.extend_from_slice(&4u32.to_be_bytes());
+        vaa.push(1);
+        vaa.extend_from_slice(&[0; 66]);
+        vaa.extend_from_slice(&[0; 51]);
+        vaa.extend(
+            to_vec::<_, byteorder::BE>(&WormholeMessage::new(WormholePayload::Merkle(
+                WormholeMerkleRoot {
+                    slot:      1000,
+                    ring_size: 10,
+                    root:      tree.root.as_bytes().try_into().unwrap(),
+                },
+            )))
+            .unwrap(),
+        );
+
+        let updates = messages
+            .iter()
+            .map(|message| MerklePriceUpdate {
+                message: message.clone().into(),
+                proof:   tree.prove(message).unwrap(),
+            })
+            .collect();
+        to_vec::<_, byteorder::BE>(&AccumulatorUpdateData::new(Proof::WormholeMerkle {
+            vaa: vaa.into(),
+            updates,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let messages = [price_feed_message(1, 100), price_feed_message(2, 200)];
+        let data = accumulator_update_data(&messages.map(Message::PriceFeedMessage));
+        let update = AccumulatorUpdate::try_from_slice(&data).unwrap();
+
+        assert_eq!(update.guardian_set_index, 4);
+        assert_eq!(update.slot, 1000);
+        assert_eq!(update.vaa[0], 1);
+        assert_eq!(update.updates.len(), 2);
+        for (update, message) in update.updates.iter().zip(messages) {
+            assert_eq!(update.message, PriceFeedMessage::from(message));
+            assert_eq!(
+                update.merkle_price_update.message,
+                to_vec::<_, byteorder::BE>(&Message::PriceFeedMessage(message)).unwrap()
+            );
+            assert!(!update.merkle_price_update.proof.is_empty());
+        }
+    }
+
+    #[test]
+    fn skip_unsupported_messages() {
+        let twap_message = Message::TwapMessage(TwapMessage {
+            feed_id:           [3; 32],
+            cumulative_price:  100,
+            cumulative_conf:   10,
+            num_down_slots:    0,
+            exponent:          -8,
+            publish_time:      100,
+            prev_publish_time: 99,
+            publish_slot:      1000,
+        });
+        let data = accumulator_update_data(&[
+            twap_message.clone(),
+            Message::PriceFeedMessage(price_feed_message(1, 100)),
+        ]);
+        let update = AccumulatorUpdate::try_from_slice(&data).unwrap();
+        assert_eq!(update.updates.len(), 1);
+        assert_eq!(
+            update.updates[0].message,
+            PriceFeedMessage::from(price_feed_message(1, 100))
+        );
+
+        let data = accumulator_update_data(&[twap_message]);
+        let update = AccumulatorUpdate::try_from_slice(&data).unwrap();
+        assert!(update.updates.is_empty());
+    }
+
+    #[test]
+    fn errors() {
+        let data =
+            accumulator_update_data(&[Message::PriceFeedMessage(price_feed_message(1, 100))]);
+
+        let mut bad_magic = data.clone();
+        bad_magic[0] = b'X';
+        assert_eq!(
+            AccumulatorUpdate::try_from_slice(&bad_magic),
+            Err(AccumulatorError::InvalidMagic)
+        );
+
+        let mut bad_version = data.clone();
+        bad_version[4] = 2;
+        assert_eq!(
+            AccumulatorUpdate::try_from_slice(&bad_version),
+            Err(AccumulatorError::InvalidVersion)
+        );
+
+        for length in [0, 4, 5, data.len() / 2, data.len() - 1] {
+            assert!(AccumulatorUpdate::try_from_slice(&data[..length]).is_err());
+        }
+        assert_eq!(
+            AccumulatorUpdate::try_from_slice(&data[..data.len() - 1]),
+            Err(AccumulatorError::InvalidData)
+        );
+        assert_eq!(
+            AccumulatorUpdate::try_from_slice(&data[..4]),
+            Err(AccumulatorError::InvalidData)
+        );
+    }
+
+    #[test]
+    fn verify() {
+        let messages = [
+            price_feed_message(1, 100),
+            price_feed_message(2, 200),
+            price_feed_message(3, 300),
+        ];
+        let data = accumulator_update_data(&messages.map(Message::PriceFeedMessage));
+        let update = AccumulatorUpdate::try_from_slice(&data).unwrap();
+
+        for (price_feed_update, message) in update.updates.iter().zip(messages) {
+            assert_eq!(
+                verify_price_feed_message(
+                    &update.merkle_root,
+                    &price_feed_update.merkle_price_update
+                ),
+                Ok(PriceFeedMessage::from(message))
+            );
+        }
+
+        let mut wrong_message = update.updates[0].merkle_price_update.clone();
+        wrong_message.message = update.updates[1].merkle_price_update.message.clone();
+        assert_eq!(
+            verify_price_feed_message(&update.merkle_root, &wrong_message),
+            Err(AccumulatorError::InvalidMerkleProof)
+        );
+
+        let mut wrong_proof = update.updates[0].merkle_price_update.clone();
+        wrong_proof.proof.pop();
+        assert_eq!(
+            verify_price_feed_message(&update.merkle_root, &wrong_proof),
+            Err(AccumulatorError::InvalidMerkleProof)
+        );
+
+        assert_eq!(
+            verify_price_feed_message(&[0; 20], &update.updates[0].merkle_price_update),
+            Err(AccumulatorError::InvalidMerkleProof)
+        );
+    }
+}
@@ -0,0 +1,559 @@
+//! Combine several price updates for the same feed into a single `Price`.
+//!
+//! Several price update accounts may hold updates for the same feed, for example when they were posted by different write authorities or at different slots.
+//! Aggregating them limits how much a single stale or malicious writer can move the resulting price.
+use {
+    crate::{
+        check,
+        error::GetPriceError,
+        math,
+        price_update::{
+            FeedId,
+            MaxAge,
+            Price,
+            PriceChecks,
+            PriceUpdateV2,
+            Rounding,
+        },
+    },
+    solana_program::clock::Clock,
+};
+
+/// How to combine several prices for the same feed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Aggregation {
+    /// Use the price with the latest `publish_time`.
+    Freshest,
+    /// Use the median of the prices, after rejecting the outliers that are more than `maximum_deviation` confidence intervals away from the median of all the prices.
+    Median { maximum_deviation: u64 },
+    /// Use the mean of the prices weighted by the inverse of their variance, after rejecting the outliers that are more than `maximum_deviation` confidence intervals away
+    /// from the median of all the prices. See [`confidence_weighted_mean`].
+    ConfidenceWeighted { maximum_deviation: u64 },
+}
+
+/// Get a single `Price` for a given `FeedId` by aggregating several `PriceUpdateV2` accounts.
+///
+/// Each price update must pass `checks`, like in [`PriceUpdateV2::get_price_with_checks`], updates that don't are ignored.
+/// If fewer than `minimum_valid_updates` of them pass (or none of them, if `minimum_valid_updates` is 0), the error of the first failing update is returned.
+/// The remaining prices are then combined according to `aggregation`. With `Median` and `ConfidenceWeighted`, at least `minimum_valid_updates` prices must also
+/// survive the outlier rejection, otherwise `GetPriceError::NoValidPriceUpdates` is returned.
+///
+/// Pass the number of price updates as `minimum_valid_updates` to fail if any of them fails.
+pub fn aggregate_price_with_checks(
+    price_updates: &[&PriceUpdateV2],
+    clock: &Clock,
+    checks: PriceChecks,
+    feed_id: &FeedId,
+    aggregation: Aggregation,
+    minimum_valid_updates: usize,
+) -> std::result::Result<Price, GetPriceError> {
+    let mut first_error = None;
+    let prices: Vec<Price> = price_updates
+        .iter()
+        .filter_map(|price_update| {
+            price_update
+                .get_price_with_checks(clock, checks, feed_id)
+                .map_err(|error| first_error.get_or_insert(error))
+                .ok()
+        })
+        .collect();
+    if prices.len() < minimum_valid_updates.max(1) {
+        return Err(first_error.unwrap_or(GetPriceError::NoValidPriceUpdates));
+    }
+
+    match aggregation {
+        Aggregation::Freshest => freshest(&prices),
+        Aggregation::Median { maximum_deviation } => median(&reject_outliers_with_minimum(
+            &prices,
+            maximum_deviation,
+            minimum_valid_updates,
+        )?),
+        Aggregation::ConfidenceWeighted { maximum_deviation } => confidence_weighted_mean(
+            &reject_outliers_with_minimum(&prices, maximum_deviation, minimum_valid_updates)?,
+        ),
+    }
+}
+
+/// Get a single `Price` for a given `FeedId` by aggregating several `PriceUpdateV2` accounts, with `Full` verification.
+///
+/// See [`aggregate_price_with_checks`] for how the price updates are checked and combined.
+///
+/// # Example
+#[cfg_attr(feature = "anchor", doc = "```")]
+#[cfg_attr(not(feature = "anchor"), doc = "```ignore")]
+/// use pyth_solana_receiver_sdk::{aggregation::{aggregate_price_no_older_than, Aggregation}, price_update::{get_feed_id_from_hex, PriceUpdateV2}};
+/// use anchor_lang::prelude::*;
+///
+/// const MAXIMUM_AGE : u64 = 30;
+/// const MAXIMUM_DEVIATION : u64 = 3; // In confidence intervals
+/// const MINIMUM_VALID_UPDATES : usize = 2;
+/// const FEED_ID: &str = "0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d"; // SOL/USD
+///
+/// #[derive(Accounts)]
+/// pub struct ReadPriceAccounts<'info> {
+///     pub first_price_update: Account<'info, PriceUpdateV2>,
+///     pub second_price_update: Account<'info, PriceUpdateV2>,
+///     pub third_price_update: Account<'info, PriceUpdateV2>,
+/// }
+///
+/// pub fn read_price_accounts(ctx : Context<ReadPriceAccounts>) -> Result<()> {
+///     let price = aggregate_price_no_older_than(
+///         &[&ctx.accounts.first_price_update, &ctx.accounts.second_price_update, &ctx.accounts.third_price_update],
+///         &Clock::get()?,
+///         MAXIMUM_AGE,
+///         &get_feed_id_from_hex(FEED_ID)?,
+///         Aggregation::Median { maximum_deviation: MAXIMUM_DEVIATION },
+///         MINIMUM_VALID_UPDATES,
+///     )?;
+///     Ok(())
+/// }
+///```
+pub fn aggregate_price_no_older_than(
+    price_updates: &[&PriceUpdateV2],
+    clock: &Clock,
+    maximum_age: u64,
+    feed_id: &FeedId,
+    aggregation: Aggregation,
+    minimum_valid_updates: usize,
+) -> std::result::Result<Price, GetPriceError> {
+    aggregate_price_with_checks(
+        price_updates,
+        clock,
+        PriceChecks::new(MaxAge::Seconds(maximum_age)),
+        feed_id,
+        aggregation,
+        minimum_valid_updates,
+    )
+}
+
+/// Get the price with the latest `publish_time`. If several prices share the latest `publish_time`, the first of them is returned.
+pub fn freshest(prices: &[Price]) -> std::result::Result<Price, GetPriceError> {
+    prices
+        .iter()
+        .copied()
+        .reduce(|freshest, price| {
+            if price.publish_time > freshest.publish_time {
+                price
+            } else {
+                freshest
+            }
+        })
+        .ok_or(GetPriceError::NoValidPriceUpdates)
+}
+
+/// Get the median of `prices`, expressed with the exponent of the first price.
+///
+/// With an even number of prices, `price` is the mean of the two middle prices rounded to the nearest integer and `conf` is the mean of their confidence intervals rounded up.
+/// `publish_time` is the earliest `publish_time` of `prices`.
+pub fn median(prices: &[Price]) -> std::result::Result<Price, GetPriceError> {
+    let mut prices = with_common_exponent(prices)?;
+    prices.sort_unstable_by_key(|price| price.price);
+    let publish_time = earliest_publish_time(&prices)?;
+    let middle = prices.len() / 2;
+    let (price, conf) = if prices.len() % 2 == 1 {
+        (prices[middle].price, prices[middle].conf)
+    } else {
+        let (low, high) = (&prices[middle - 1], &prices[middle]);
+        (
+            mean(
+                i128::from(low.price) + i128::from(high.price),
+                2,
+                Rounding::Nearest,
+            )?,
+            mean(
+                i128::from(low.conf) + i128::from(high.conf),
+                2,
+                Rounding::Up,
+            )?,
+        )
+    };
+    Ok(Price {
+        price,
+        conf,
+        exponent: prices[0].exponent,
+        publish_time,
+    })
+}
+
+/// Get the mean of `prices` weighted by the inverse of their variance `conf^2`, expressed with the exponent of the first price.
+///
+/// Prices with tighter confidence intervals weigh more. If some prices have a confidence interval of zero, only those prices are averaged.
+/// `price` is rounded to the nearest integer and `conf` is the mean of the confidence intervals with the same weights, rounded up.
+/// The confidence interval is not narrowed as it would be for independent measurements, since all the prices come from the same feed.
+/// `publish_time` is the earliest `publish_time` of `prices`.
+pub fn confidence_weighted_mean(prices: &[Price]) -> std::result::Result<Price, GetPriceError> {
+    let prices = with_common_exponent(prices)?;
+    let publish_time = earliest_publish_time(&prices)?;
+    let minimum_conf = prices
+        .iter()
+        .map(|price| price.conf)
+        .min()
+        .ok_or(GetPriceError::NoValidPriceUpdates)?;
+
+    let mut total_weight: i128 = 0;
+    let mut weighted_price: i128 = 0;
+    let mut weighted_conf: i128 = 0;
+    for price in &prices {
+        let weight = variance_weight(price.conf, minimum_conf);
+        total_weight += weight;
+        weighted_price = weighted_price
+            .checked_add(weight * i128::from(price.price))
+            .ok_or(GetPriceError::PriceOverflow)?;
+        weighted_conf = weighted_conf
+            .checked_add(weight * i128::from(price.conf))
+            .ok_or(GetPriceError::PriceOverflow)?;
+    }
+
+    Ok(Price {
+        price: mean(weighted_price, total_weight, Rounding::Nearest)?,
+        conf: mean(weighted_conf, total_weight, Rounding::Up)?,
+        exponent: prices[0].exponent,
+        publish_time,
+    })
+}
+
+/// Remove the prices that are more than `maximum_deviation` of their own confidence intervals away from the median of `prices`.
+/// The remaining prices are expressed with the exponent of the first price.
+///
+/// Returns `GetPriceError::NoValidPriceUpdates` if all the prices are rejected.
+pub fn reject_outliers(
+    prices: &[Price],
+    maximum_deviation: u64,
+) -> std::result::Result<Vec<Price>, GetPriceError> {
+    let reference = median(prices)?.price;
+    let prices: Vec<Price> = with_common_exponent(prices)?
+        .into_iter()
+        .filter(|price| {
+            (i128::from(price.price) - i128::from(reference)).unsigned_abs()
+                <= u128::from(maximum_deviation) * u128::from(price.conf)
+        })
+        .collect();
+    if prices.is_empty() {
+        return Err(GetPriceError::NoValidPriceUpdates);
+    }
+    Ok(prices)
+}
+
+/// Like [`reject_outliers`], but also fails if fewer than `minimum_valid_updates` prices remain.
+fn reject_outliers_with_minimum(
+    prices: &[Price],
+    maximum_deviation: u64,
+    minimum_valid_updates: usize,
+) -> std::result::Result<Vec<Price>, GetPriceError> {
+    let prices = reject_outliers(prices, maximum_deviation)?;
+    check!(
+        prices.len() >= minimum_valid_updates,
+        GetPriceError::NoValidPriceUpdates
+    );
+    Ok(prices)
+}
+
+/// Rescale all the prices to the exponent of the first one.
+fn with_common_exponent(prices: &[Price]) -> std::result::Result<Vec<Price>, GetPriceError> {
+    let exponent = prices
+        .first()
+        .ok_or(GetPriceError::NoValidPriceUpdates)?
+        .exponent;
+    prices
+        .iter()
+        .map(|price| price.scale_to_exponent(exponent, Rounding::Nearest))
+        .collect()
+}
+
+fn earliest_publish_time(prices: &[Price]) -> std::result::Result<i64, GetPriceError> {
+    prices
+        .iter()
+        .map(|price| price.publish_time)
+        .min()
+        .ok_or(GetPriceError::NoValidPriceUpdates)
+}
+
+/// The weight of a price with confidence interval `conf` in a mean weighted by the inverse of the variance, as a fixed-point number with 32 fractional bits.
+/// The weights are relative to the smallest confidence interval `minimum_conf`, whose weight is 1.
+fn variance_weight(conf: u64, minimum_conf: u64) -> i128 {
+    if conf == 0 {
+        return 1 << 32;
+    }
+    if minimum_conf == 0 {
+        return 0;
+    }
+    // ratio = minimum_conf / conf <= 1 with 32 fractional bits, weight = ratio^2
+    let ratio = (u128::from(minimum_conf) << 32) / u128::from(conf);
+    ((ratio * ratio) >> 32) as i128
+}
+
+/// `sum / count` with the given rounding, as long as the result is a valid `T`.
+fn mean<T: TryFrom<i128>>(
+    sum: i128,
+    count: i128,
+    rounding: Rounding,
+) -> std::result::Result<T, GetPriceError> {
+    math::mul_pow10_div(sum, 0, count, rounding)
+        .and_then(|mean| T::try_from(mean).ok())
+        .ok_or(GetPriceError::PriceOverflow)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use {
+        super::{
+            aggregate_price_no_older_than,
+            aggregate_price_with_checks,
+            confidence_weighted_mean,
+            freshest,
+            median,
+            reject_outliers,
+            Aggregation,
+        },
+        crate::{
+            error::GetPriceError,
+            price_update::{
+                FeedId,
+                MaxAge,
+                Price,
+                PriceChecks,
+                PriceUpdateV2,
+                VerificationLevel,
+            },
+            test_utils::price_update,
+        },
+        solana_program::clock::Clock,
+    };
+
+    fn price(price: i64, conf: u64, publish_time: i64) -> Price {
+        Price {
+            price,
+            conf,
+            exponent: -8,
+            publish_time,
+        }
+    }
+
+    #[test]
+    fn aggregate() {
+        let feed_id = FeedId::new([1; 32]);
+        let clock = Clock {
+            unix_timestamp: 100,
+            ..Clock::default()
+        };
+        let price_updates = [
+            price_update(feed_id, 1000, 10, 99),
+            price_update(feed_id, 1010, 10, 98),
+            price_update(feed_id, 5000, 10, 100), // Outlier
+            price_update(feed_id, 1005, 10, 50),  // Too old
+            price_update(FeedId::new([2; 32]), 1005, 10, 100),
+        ];
+        let price_updates: Vec<&PriceUpdateV2> = price_updates.iter().collect();
+
+        assert_eq!(
+            aggregate_price_no_older_than(
+                &price_updates,
+                &clock,
+                10,
+                &feed_id,
+                Aggregation::Freshest,
+                1
+            ),
+            Ok(price(5000, 10, 100))
+        );
+        assert_eq!(
+            aggregate_price_no_older_than(
+                &price_updates,
+                &clock,
+                10,
+                &feed_id,
+                Aggregation::Median {
+                    maximum_deviation: 3,
+                },
+                1
+            ),
+            Ok(price(1005, 10, 98))
+        );
+        assert_eq!(
+            aggregate_price_no_older_than(
+                &price_updates,
+                &clock,
+                10,
+                &feed_id,
+                Aggregation::ConfidenceWeighted {
+                    maximum_deviation: 3,
+                },
+                1
+            ),
+            Ok(price(1005, 10, 98))
+        );
+        assert_eq!(
+            aggregate_price_no_older_than(
+                &price_updates[3..4],
+                &clock,
+                10,
+                &feed_id,
+                Aggregation::Freshest,
+                1
+            ),
+            Err(GetPriceError::PriceTooOld)
+        );
+        assert_eq!(
+            aggregate_price_no_older_than(&[], &clock, 10, &feed_id, Aggregation::Freshest, 0),
+            Err(GetPriceError::NoValidPriceUpdates)
+        );
+
+        let mut partial_update = price_update(feed_id, 1020, 10, 100);
+        partial_update.verification_level = VerificationLevel::Partial { num_signatures: 5 };
+        let checks = PriceChecks::new(MaxAge::from_seconds(10));
+        assert_eq!(
+            aggregate_price_with_checks(
+                &[&partial_update],
+                &clock,
+                checks,
+                &feed_id,
+                Aggregation::Freshest,
+                1
+            ),
+            Err(GetPriceError::InsufficientVerificationLevel)
+        );
+        assert_eq!(
+            aggregate_price_with_checks(
+                &[&partial_update],
+                &clock,
+                checks.with_verification_level(VerificationLevel::Partial { num_signatures: 5 }),
+                &feed_id,
+                Aggregation::Freshest,
+                1
+            ),
+            Ok(price(1020, 10, 100))
+        );
+    }
+
+    #[test]
+    fn minimum_valid_updates() {
+        let feed_id = FeedId::new([1; 32]);
+        let clock = Clock {
+            unix_timestamp: 100,
+            ..Clock::default()
+        };
+        let price_updates = [
+            price_update(feed_id, 1000, 1, 99), // Outlier
+            price_update(feed_id, 1500, 1, 98),
+            price_update(feed_id, 2000, 1, 97), // Outlier
+            price_update(feed_id, 1500, 1, 50), // Too old
+        ];
+        let price_updates: Vec<&PriceUpdateV2> = price_updates.iter().collect();
+        let median = Aggregation::Median {
+            maximum_deviation: 3,
+        };
+
+        // Only one update passes the checks and the outlier rejection
+        assert_eq!(
+            aggregate_price_no_older_than(&price_updates, &clock, 10, &feed_id, median, 1),
+            Ok(price(1500, 1, 98))
+        );
+        assert_eq!(
+            aggregate_price_no_older_than(&price_updates, &clock, 10, &feed_id, median, 2),
+            Err(GetPriceError::NoValidPriceUpdates)
+        );
+        assert_eq!(
+            aggregate_price_no_older_than(
+                &price_updates,
+                &clock,
+                10,
+                &feed_id,
+                Aggregation::ConfidenceWeighted {
+                    maximum_deviation: 3,
+                },
+                2
+            ),
+            Err(GetPriceError::NoValidPriceUpdates)
+        );
+
+        // Freshest doesn't reject outliers, but all the updates must pass the checks
+        assert_eq!(
+            aggregate_price_no_older_than(
+                &price_updates[..3],
+                &clock,
+                10,
+                &feed_id,
+                Aggregation::Freshest,
+                3
+            ),
+            Ok(price(1000, 1, 99))
+        );
+        assert_eq!(
+            aggregate_price_no_older_than(
+                &price_updates,
+                &clock,
+                10,
+                &feed_id,
+                Aggregation::Freshest,
+                4
+            ),
+            Err(GetPriceError::PriceTooOld)
+        );
+        assert_eq!(
+            aggregate_price_no_older_than(
+                &price_updates[..3],
+                &clock,
+                10,
+                &feed_id,
+                Aggregation::Freshest,
+                4
+            ),
+            Err(GetPriceError::NoValidPriceUpdates)
+        );
+    }
+
+    #[test]
+    fn freshest_and_median() {
+        let prices = [price(30, 1, 5), price(10, 2, 7), price(20, 4, 7)];
+        assert_eq!(freshest(&prices), Ok(price(10, 2, 7)));
+        assert_eq!(median(&prices), Ok(price(20, 4, 5)));
+        assert_eq!(median(&prices[..2]), Ok(price(20, 2, 5)));
+        assert_eq!(
+            median(&[price(-3, 1, 0), price(0, 2, 0)]),
+            Ok(price(-2, 2, 0))
+        );
+        assert_eq!(median(&[]), Err(GetPriceError::NoValidPriceUpdates));
+
+        // Prices are rescaled to the exponent of the first one
+        let rescaled = Price {
+            price:        3,
+            conf:         0,
+            exponent:     -7,
+            publish_time: 0,
+        };
+        assert_eq!(
+            median(&[price(10, 0, 0), rescaled, price(40, 0, 0)]),
+            Ok(price(30, 0, 0))
+        );
+    }
+
+    #[test]
+    fn confidence_weighted() {
+        // Weights are 1 and 1/4
+        assert_eq!(
+            confidence_weighted_mean(&[price(100, 10, 0), price(200, 20, 0)]),
+            Ok(price(120, 12, 0))
+        );
+        // Prices without uncertainty take precedence
+        assert_eq!(
+            confidence_weighted_mean(&[price(100, 0, 0), price(110, 0, 0), price(200, 1, 0)]),
+            Ok(price(105, 0, 0))
+        );
+        assert_eq!(
+            confidence_weighted_mean(&[price(i64::MAX, u64::MAX, 0), price(i64::MAX, u64::MAX, 0)]),
+            Ok(price(i64::MAX, u64::MAX, 0))
+        );
+    }
+
+    #[test]
+    fn outliers() {
+        let prices = [price(100, 1, 0), price(102, 1, 0), price(110, 3, 0)];
+        assert_eq!(reject_outliers(&prices, 2), Ok(prices[..2].to_vec()));
+        assert_eq!(reject_outliers(&prices, 0), Ok(prices[1..2].to_vec()));
+        assert_eq!(reject_outliers(&prices, 8), Ok(prices.to_vec()));
+        assert_eq!(
+            reject_outliers(&prices[..2], 0),
+            Err(GetPriceError::NoValidPriceUpdates)
+        );
+    }
+}
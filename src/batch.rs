@@ -0,0 +1,253 @@
+use {
+    crate::{
+        check,
+        error::GetPriceError,
+        price_update::{
+            FeedId,
+            MaxAge,
+            Price,
+            PriceChecks,
+        },
+        price_update_view::PriceUpdateV2View,
+    },
+    solana_program::{
+        account_info::AccountInfo,
+        clock::Clock,
+        msg,
+        program_error::ProgramError,
+    },
+    std::fmt,
+};
+
+/// The error returned when reading a batch of price update accounts, with the index of the account that failed its checks.
+///
+/// If the number of accounts doesn't match the number of feed ids, `error` is `GetPriceError::WrongNumberOfAccounts` and `index` is the length of the shorter list.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatchPriceError {
+    pub index: usize,
+    pub error: GetPriceError,
+}
+
+impl fmt::Display for BatchPriceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Price update account {}: {}", self.index, self.error)
+    }
+}
+
+impl std::error::Error for BatchPriceError {}
+
+/// The resulting Anchor error has the code of `error`, and the index of the failing account as its account name so that it shows up in the logs.
+#[cfg(feature = "anchor")]
+impl From<BatchPriceError> for anchor_lang::error::Error {
+    fn from(error: BatchPriceError) -> Self {
+        anchor_lang::error::Error::from(error.error)
+            .with_account_name(format!("price update account {}", error.index))
+    }
+}
+
+/// Allows programs that don't use Anchor to propagate a `BatchPriceError` with `?`.
+/// The index of the failing account is logged, since it can't be part of a `ProgramError`.
+impl From<BatchPriceError> for ProgramError {
+    fn from(error: BatchPriceError) -> Self {
+        msg!("{}", error);
+        error.error.into()
+    }
+}
+
+/// Get a `Price` for each of `feed_ids` from the matching price update account in `accounts`, if it passes `checks`.
+/// The prices are returned in the same order as `feed_ids`.
+///
+/// The accounts are read in place without being deserialized, and each of them is checked to be:
+/// - owned by the Pyth Solana Receiver program
+/// - a `PriceUpdateV2` account
+/// - for the feed id at the same index in `feed_ids`
+/// - passing `checks`, see [`PriceChecks`]
+pub fn get_prices_with_checks(
+    accounts: &[AccountInfo],
+    feed_ids: &[FeedId],
+    clock: &Clock,
+    checks: PriceChecks,
+) -> std::result::Result<Vec<Price>, BatchPriceError> {
+    if accounts.len() != feed_ids.len() {
+        return Err(BatchPriceError {
+            index: accounts.len().min(feed_ids.len()),
+            error: GetPriceError::WrongNumberOfAccounts,
+        });
+    }
+    accounts
+        .iter()
+        .zip(feed_ids)
+        .enumerate()
+        .map(|(index, (account, feed_id))| {
+            get_account_price(account, feed_id, clock, checks)
+                .map_err(|error| BatchPriceError { index, error })
+        })
+        .collect()
+}
+
+/// Get a `Price` for each of `feed_ids` from the matching price update account in `accounts`, no older than `maximum_age`, with `Full` verification.
+/// The prices are returned in the same order as `feed_ids`.
+///
+/// See [`get_prices_with_checks`] for the checks performed on each account.
+///
+/// # Example
+#[cfg_attr(feature = "anchor", doc = "```")]
+#[cfg_attr(not(feature = "anchor"), doc = "```ignore")]
+/// use pyth_solana_receiver_sdk::{batch::get_prices_no_older_than, feed_id, price_update::FeedId};
+/// use anchor_lang::prelude::*;
+///
+/// const MAXIMUM_AGE : u64 = 30;
+/// const FEED_IDS: [FeedId; 2] = [
+///     feed_id!("0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d"), // SOL/USD
+///     feed_id!("0xe62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43"), // BTC/USD
+/// ];
+///
+/// #[derive(Accounts)]
+/// pub struct ReadPriceAccounts {}
+///
+/// pub fn read_price_accounts(ctx : Context<ReadPriceAccounts>) -> Result<()> {
+///     let prices = get_prices_no_older_than(ctx.remaining_accounts, &FEED_IDS, &Clock::get()?, MAXIMUM_AGE)?;
+///     Ok(())
+/// }
+///```
+pub fn get_prices_no_older_than(
+    accounts: &[AccountInfo],
+    feed_ids: &[FeedId],
+    clock: &Clock,
+    maximum_age: u64,
+) -> std::result::Result<Vec<Price>, BatchPriceError> {
+    get_prices_with_checks(
+        accounts,
+        feed_ids,
+        clock,
+        PriceChecks::new(MaxAge::Seconds(maximum_age)),
+    )
+}
+
+fn get_account_price(
+    account: &AccountInfo,
+    feed_id: &FeedId,
+    clock: &Clock,
+    checks: PriceChecks,
+) -> std::result::Result<Price, GetPriceError> {
+    check!(
+        *account.owner == crate::ID,
+        GetPriceError::WrongAccountOwner
+    );
+    let data = account
+        .try_borrow_data()
+        .map_err(|_| GetPriceError::InvalidAccountData)?;
+    PriceUpdateV2View::try_from_account_data(&data)?.get_price_with_checks(clock, checks, feed_id)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use {
+        super::{
+            get_prices_no_older_than,
+            get_prices_with_checks,
+            BatchPriceError,
+        },
+        crate::{
+            error::GetPriceError,
+            price_update::{
+                FeedId,
+                MaxAge,
+                PriceChecks,
+                PriceUpdateV2,
+                VerificationLevel,
+            },
+            test_utils::{
+                self,
+                price_update,
+            },
+        },
+        solana_program::{
+            account_info::AccountInfo,
+            clock::Clock,
+            pubkey::Pubkey,
+        },
+    };
+
+    fn account_data(feed_id: FeedId, price: i64, publish_time: i64) -> Vec<u8> {
+        test_utils::account_data(&PriceUpdateV2 {
+            verification_level: VerificationLevel::Partial { num_signatures: 5 },
+            ..price_update(feed_id, price, 0, publish_time)
+        })
+    }
+
+    #[test]
+    fn batch() {
+        let feed_ids = [FeedId::new([1; 32]), FeedId::new([2; 32])];
+        let clock = Clock {
+            unix_timestamp: 100,
+            ..Clock::default()
+        };
+        let keys = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut lamports = [0, 0];
+        let mut data = [
+            account_data(feed_ids[0], 10, 100),
+            account_data(feed_ids[1], 20, 80),
+        ];
+        let owners = [crate::ID, Pubkey::new_unique()];
+        let [lamports_0, lamports_1] = &mut lamports;
+        let [data_0, data_1] = &mut data;
+        let accounts = [
+            AccountInfo::new(
+                &keys[0], false, false, lamports_0, data_0, &owners[0], false, 0,
+            ),
+            AccountInfo::new(
+                &keys[1], false, false, lamports_1, data_1, &owners[0], false, 0,
+            ),
+        ];
+        let partial = |maximum_age| {
+            PriceChecks::new(MaxAge::from_seconds(maximum_age))
+                .with_verification_level(VerificationLevel::Partial { num_signatures: 5 })
+        };
+
+        let prices = get_prices_with_checks(&accounts, &feed_ids, &clock, partial(20)).unwrap();
+        assert_eq!(
+            prices.iter().map(|price| price.price).collect::<Vec<_>>(),
+            vec![10, 20]
+        );
+
+        assert_eq!(
+            get_prices_no_older_than(&accounts, &feed_ids, &clock, 20),
+            Err(BatchPriceError {
+                index: 0,
+                error: GetPriceError::InsufficientVerificationLevel,
+            })
+        );
+        assert_eq!(
+            get_prices_with_checks(&accounts, &feed_ids, &clock, partial(19)),
+            Err(BatchPriceError {
+                index: 1,
+                error: GetPriceError::PriceTooOld,
+            })
+        );
+        assert_eq!(
+            get_prices_with_checks(&accounts, &[feed_ids[1], feed_ids[0]], &clock, partial(20)),
+            Err(BatchPriceError {
+                index: 0,
+                error: GetPriceError::MismatchedFeedId,
+            })
+        );
+        assert_eq!(
+            get_prices_with_checks(&accounts[..1], &feed_ids, &clock, partial(20)),
+            Err(BatchPriceError {
+                index: 1,
+                error: GetPriceError::WrongNumberOfAccounts,
+            })
+        );
+
+        let mut accounts = accounts;
+        accounts[1].owner = &owners[1];
+        assert_eq!(
+            get_prices_with_checks(&accounts, &feed_ids, &clock, partial(20)),
+            Err(BatchPriceError {
+                index: 1,
+                error: GetPriceError::WrongAccountOwner,
+            })
+        );
+    }
+}
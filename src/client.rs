@@ -0,0 +1,466 @@
+//! Instruction builders for the Pyth Solana Receiver program, for clients that post price updates from Rust,
+//! and for the Pyth Push Oracle program, which uses the receiver to keep its price feed accounts up to date.
+//!
+//! The instruction arguments mirror the ones of the program and are serialized the same way, with the Anchor discriminator of each instruction as a prefix.
+use {
+    crate::{
+        config::{
+            Config,
+            DataSource,
+        },
+        pda::{
+            get_config_address,
+            get_price_feed_address,
+            get_treasury_address,
+        },
+        price_update::FeedId,
+        PYTH_PUSH_ORACLE_ID,
+    },
+    borsh::{
+        BorshDeserialize,
+        BorshSerialize,
+    },
+    solana_program::{
+        instruction::{
+            AccountMeta,
+            Instruction,
+        },
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+/// A price update message along with its Merkle proof of inclusion in the Merkle root signed by the Wormhole guardians.
+///
+/// It's serialized like `pythnet_sdk::wire::v1::MerklePriceUpdate`, which the program uses.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MerklePriceUpdate {
+    pub message: Vec<u8>,
+    pub proof:   Vec<[u8; 20]>,
+}
+
+impl From<pythnet_sdk::wire::v1::MerklePriceUpdate> for MerklePriceUpdate {
+    fn from(update: pythnet_sdk::wire::v1::MerklePriceUpdate) -> Self {
+        MerklePriceUpdate {
+            message: update.message.into(),
+            proof:   update.proof.to_vec(),
+        }
+    }
+}
+
+/// The arguments of the `post_update` instruction, which verifies a price update against a Wormhole VAA previously posted to an encoded VAA account.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PostUpdateParams {
+    pub merkle_price_update: MerklePriceUpdate,
+    pub treasury_id:         u8,
+}
+
+/// The arguments of the `post_update_atomic` instruction, which verifies a price update against a Wormhole VAA included in the instruction.
+/// The VAA's signatures are checked by the instruction, so a VAA with fewer signatures results in a `Partial` verification level.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PostUpdateAtomicParams {
+    pub vaa:                 Vec<u8>,
+    pub merkle_price_update: MerklePriceUpdate,
+    pub treasury_id:         u8,
+}
+
+// Anchor discriminators, the first 8 bytes of `sha256("global:<instruction name>")`
+const INITIALIZE_DISCRIMINATOR: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+const REQUEST_GOVERNANCE_AUTHORITY_TRANSFER_DISCRIMINATOR: [u8; 8] =
+    [92, 18, 67, 156, 27, 151, 183, 224];
+const CANCEL_GOVERNANCE_AUTHORITY_TRANSFER_DISCRIMINATOR: [u8; 8] =
+    [39, 93, 70, 137, 137, 90, 248, 154];
+const ACCEPT_GOVERNANCE_AUTHORITY_TRANSFER_DISCRIMINATOR: [u8; 8] =
+    [254, 39, 222, 79, 64, 217, 205, 127];
+const SET_DATA_SOURCES_DISCRIMINATOR: [u8; 8] = [107, 73, 15, 119, 195, 116, 91, 210];
+const SET_FEE_DISCRIMINATOR: [u8; 8] = [18, 154, 24, 18, 237, 214, 19, 80];
+const SET_WORMHOLE_ADDRESS_DISCRIMINATOR: [u8; 8] = [154, 174, 252, 157, 91, 215, 179, 156];
+const SET_MINIMUM_SIGNATURES_DISCRIMINATOR: [u8; 8] = [5, 210, 206, 124, 43, 68, 104, 149];
+pub(crate) const POST_UPDATE_ATOMIC_DISCRIMINATOR: [u8; 8] = [49, 172, 84, 192, 175, 180, 52, 234];
+pub(crate) const POST_UPDATE_DISCRIMINATOR: [u8; 8] = [133, 95, 207, 175, 11, 79, 118, 44];
+pub(crate) const RECLAIM_RENT_DISCRIMINATOR: [u8; 8] = [218, 200, 19, 197, 227, 89, 192, 22];
+// Discriminator of the Pyth Push Oracle program's only instruction
+pub(crate) const UPDATE_PRICE_FEED_DISCRIMINATOR: [u8; 8] = [28, 9, 93, 150, 86, 153, 188, 115];
+
+/// Build a `post_update` instruction, which writes the price update in `params` to `price_update_account`.
+///
+/// `encoded_vaa` must be a Wormhole encoded VAA account containing the VAA that signs the Merkle root of the price update.
+/// `price_update_account` must sign the transaction, and either be a new account or an existing account whose write authority is `write_authority`.
+/// The update fee is paid by `payer` to the treasury of `params.treasury_id`.
+pub fn post_update(
+    payer: &Pubkey,
+    encoded_vaa: &Pubkey,
+    price_update_account: &Pubkey,
+    write_authority: &Pubkey,
+    params: &PostUpdateParams,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts:   vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*encoded_vaa, false),
+            AccountMeta::new_readonly(get_config_address(), false),
+            AccountMeta::new(get_treasury_address(params.treasury_id), false),
+            AccountMeta::new(*price_update_account, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(*write_authority, true),
+        ],
+        data:       instruction_data(POST_UPDATE_DISCRIMINATOR, params),
+    }
+}
+
+/// Build a `post_update_atomic` instruction, which verifies the VAA in `params` and writes its price update to `price_update_account`.
+///
+/// `guardian_set` must be the Wormhole guardian set account that signed the VAA, see [`get_guardian_set_address`](crate::pda::get_guardian_set_address).
+/// `price_update_account` must sign the transaction, and either be a new account or an existing account whose write authority is `write_authority`.
+/// The update fee is paid by `payer` to the treasury of `params.treasury_id`.
+pub fn post_update_atomic(
+    payer: &Pubkey,
+    guardian_set: &Pubkey,
+    price_update_account: &Pubkey,
+    write_authority: &Pubkey,
+    params: &PostUpdateAtomicParams,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts:   vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*guardian_set, false),
+            AccountMeta::new_readonly(get_config_address(), false),
+            AccountMeta::new(get_treasury_address(params.treasury_id), false),
+            AccountMeta::new(*price_update_account, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(*write_authority, true),
+        ],
+        data:       instruction_data(POST_UPDATE_ATOMIC_DISCRIMINATOR, params),
+    }
+}
+
+/// Build a `reclaim_rent` instruction, which closes `price_update_account` and returns its rent to `payer`, who must be its write authority.
+pub fn reclaim_rent(payer: &Pubkey, price_update_account: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts:   vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*price_update_account, false),
+        ],
+        data:       instruction_data(RECLAIM_RENT_DISCRIMINATOR, &()),
+    }
+}
+
+/// Build an `update_price_feed` instruction of the Pyth Push Oracle program, which writes the price update in `params` to the price feed account
+/// of `shard_id` and `feed_id`, see [`get_price_feed_address`].
+///
+/// The price update must be for `feed_id` and newer than the current content of the price feed account.
+/// `encoded_vaa` must be a Wormhole encoded VAA account containing the VAA that signs the Merkle root of the price update.
+/// The update fee is paid by `payer` to the treasury of `params.treasury_id`.
+pub fn update_price_feed(
+    payer: &Pubkey,
+    encoded_vaa: &Pubkey,
+    shard_id: u16,
+    feed_id: &FeedId,
+    params: &PostUpdateParams,
+) -> Instruction {
+    Instruction {
+        program_id: PYTH_PUSH_ORACLE_ID,
+        accounts:   vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(crate::ID, false),
+            AccountMeta::new_readonly(*encoded_vaa, false),
+            AccountMeta::new_readonly(get_config_address(), false),
+            AccountMeta::new(get_treasury_address(params.treasury_id), false),
+            AccountMeta::new(get_price_feed_address(shard_id, feed_id), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data:       update_price_feed_data(params, shard_id, feed_id),
+    }
+}
+
+/// Build an `initialize` instruction, which creates the `Config` account of the program.
+pub fn initialize(payer: &Pubkey, initial_config: &Config) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts:   vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(get_config_address(), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data:       instruction_data(INITIALIZE_DISCRIMINATOR, initial_config),
+    }
+}
+
+/// Build a `request_governance_authority_transfer` instruction, the first step of transferring the governance authority to `target_governance_authority`.
+/// `payer` must be the current governance authority.
+pub fn request_governance_authority_transfer(
+    payer: &Pubkey,
+    target_governance_authority: &Pubkey,
+) -> Instruction {
+    governance_instruction(
+        payer,
+        REQUEST_GOVERNANCE_AUTHORITY_TRANSFER_DISCRIMINATOR,
+        target_governance_authority,
+    )
+}
+
+/// Build a `cancel_governance_authority_transfer` instruction. `payer` must be the current governance authority.
+pub fn cancel_governance_authority_transfer(payer: &Pubkey) -> Instruction {
+    governance_instruction(
+        payer,
+        CANCEL_GOVERNANCE_AUTHORITY_TRANSFER_DISCRIMINATOR,
+        &(),
+    )
+}
+
+/// Build an `accept_governance_authority_transfer` instruction, the second step of transferring the governance authority.
+/// `payer` must be the target governance authority of the transfer.
+pub fn accept_governance_authority_transfer(payer: &Pubkey) -> Instruction {
+    governance_instruction(
+        payer,
+        ACCEPT_GOVERNANCE_AUTHORITY_TRANSFER_DISCRIMINATOR,
+        &(),
+    )
+}
+
+/// Build a `set_data_sources` instruction, which replaces the list of valid data sources. `payer` must be the governance authority.
+pub fn set_data_sources(payer: &Pubkey, valid_data_sources: &[DataSource]) -> Instruction {
+    governance_instruction(payer, SET_DATA_SOURCES_DISCRIMINATOR, valid_data_sources)
+}
+
+/// Build a `set_fee` instruction, which sets the fee for a single price update. `payer` must be the governance authority.
+pub fn set_fee(payer: &Pubkey, single_update_fee_in_lamports: u64) -> Instruction {
+    governance_instruction(payer, SET_FEE_DISCRIMINATOR, &single_update_fee_in_lamports)
+}
+
+/// Build a `set_wormhole_address` instruction, which sets the Wormhole program that owns the VAAs and guardian sets. `payer` must be the governance authority.
+pub fn set_wormhole_address(payer: &Pubkey, wormhole: &Pubkey) -> Instruction {
+    governance_instruction(payer, SET_WORMHOLE_ADDRESS_DISCRIMINATOR, wormhole)
+}
+
+/// Build a `set_minimum_signatures` instruction, which sets the minimum number of signatures for a VAA to be accepted. `payer` must be the governance authority.
+pub fn set_minimum_signatures(payer: &Pubkey, minimum_signatures: u8) -> Instruction {
+    governance_instruction(
+        payer,
+        SET_MINIMUM_SIGNATURES_DISCRIMINATOR,
+        &minimum_signatures,
+    )
+}
+
+fn governance_instruction<T: BorshSerialize + ?Sized>(
+    payer: &Pubkey,
+    discriminator: [u8; 8],
+    args: &T,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts:   vec![
+            AccountMeta::new_readonly(*payer, true),
+            AccountMeta::new(get_config_address(), false),
+        ],
+        data:       instruction_data(discriminator, args),
+    }
+}
+
+pub(crate) fn update_price_feed_data(
+    params: &PostUpdateParams,
+    shard_id: u16,
+    feed_id: &FeedId,
+) -> Vec<u8> {
+    instruction_data(
+        UPDATE_PRICE_FEED_DISCRIMINATOR,
+        &(params, shard_id, feed_id),
+    )
+}
+
+pub(crate) fn instruction_data<T: BorshSerialize + ?Sized>(
+    discriminator: [u8; 8],
+    args: &T,
+) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    // Serializing to a `Vec` can't fail
+    args.serialize(&mut data).unwrap();
+    data
+}
+
+#[cfg(test)]
+pub mod tests {
+    use {
+        super::{
+            accept_governance_authority_transfer,
+            post_update,
+            set_data_sources,
+            set_fee,
+            update_price_feed,
+            MerklePriceUpdate,
+            PostUpdateParams,
+            ACCEPT_GOVERNANCE_AUTHORITY_TRANSFER_DISCRIMINATOR,
+            CANCEL_GOVERNANCE_AUTHORITY_TRANSFER_DISCRIMINATOR,
+            INITIALIZE_DISCRIMINATOR,
+            POST_UPDATE_ATOMIC_DISCRIMINATOR,
+            POST_UPDATE_DISCRIMINATOR,
+            RECLAIM_RENT_DISCRIMINATOR,
+            REQUEST_GOVERNANCE_AUTHORITY_TRANSFER_DISCRIMINATOR,
+            SET_DATA_SOURCES_DISCRIMINATOR,
+            SET_FEE_DISCRIMINATOR,
+            SET_MINIMUM_SIGNATURES_DISCRIMINATOR,
+            SET_WORMHOLE_ADDRESS_DISCRIMINATOR,
+            UPDATE_PRICE_FEED_DISCRIMINATOR,
+        },
+        crate::{
+            config::DataSource,
+            pda::{
+                get_config_address,
+                get_price_feed_address,
+                get_treasury_address,
+            },
+            price_update::FeedId,
+            PYTH_PUSH_ORACLE_ID,
+        },
+        borsh::BorshSerialize,
+        pythnet_sdk::{
+            accumulators::merkle::MerklePath,
+            hashers::keccak256_160::Keccak160,
+            wire::PrefixedVec,
+        },
+        solana_program::{
+            hash::hash,
+            instruction::AccountMeta,
+            pubkey::Pubkey,
+            system_program,
+        },
+    };
+
+    #[test]
+    fn discriminators() {
+        for (name, discriminator) in [
+            ("initialize", INITIALIZE_DISCRIMINATOR),
+            (
+                "request_governance_authority_transfer",
+                REQUEST_GOVERNANCE_AUTHORITY_TRANSFER_DISCRIMINATOR,
+            ),
+            (
+                "cancel_governance_authority_transfer",
+                CANCEL_GOVERNANCE_AUTHORITY_TRANSFER_DISCRIMINATOR,
+            ),
+            (
+                "accept_governance_authority_transfer",
+                ACCEPT_GOVERNANCE_AUTHORITY_TRANSFER_DISCRIMINATOR,
+            ),
+            ("set_data_sources", SET_DATA_SOURCES_DISCRIMINATOR),
+            ("set_fee", SET_FEE_DISCRIMINATOR),
+            ("set_wormhole_address", SET_WORMHOLE_ADDRESS_DISCRIMINATOR),
+            (
+                "set_minimum_signatures",
+                SET_MINIMUM_SIGNATURES_DISCRIMINATOR,
+            ),
+            ("post_update_atomic", POST_UPDATE_ATOMIC_DISCRIMINATOR),
+            ("post_update", POST_UPDATE_DISCRIMINATOR),
+            ("reclaim_rent", RECLAIM_RENT_DISCRIMINATOR),
+            ("update_price_feed", UPDATE_PRICE_FEED_DISCRIMINATOR),
+        ] {
+            assert_eq!(
+                hash(format!("global:{}", name).as_bytes()).to_bytes()[..8],
+                discriminator
+            );
+        }
+    }
+
+    #[test]
+    fn post_update_instruction() {
+        let payer = Pubkey::new_unique();
+        let encoded_vaa = Pubkey::new_unique();
+        let price_update_account = Pubkey::new_unique();
+        let params = PostUpdateParams {
+            merkle_price_update: pythnet_sdk::wire::v1::MerklePriceUpdate {
+                message: PrefixedVec::from(vec![1, 2, 3]),
+                proof:   MerklePath::<Keccak160>::new(vec![[4; 20]]),
+            }
+            .into(),
+            treasury_id:         1,
+        };
+        let instruction = post_update(&payer, &encoded_vaa, &price_update_account, &payer, &params);
+
+        assert_eq!(instruction.program_id, crate::ID);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new_readonly(encoded_vaa, false),
+                AccountMeta::new_readonly(get_config_address(), false),
+                AccountMeta::new(get_treasury_address(1), false),
+                AccountMeta::new(price_update_account, true),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(payer, true),
+            ]
+        );
+
+        let mut data = POST_UPDATE_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[3, 0, 0, 0, 1, 2, 3]);
+        data.extend_from_slice(&[1, 0, 0, 0]);
+        data.extend_from_slice(&[4; 20]);
+        data.push(1);
+        assert_eq!(instruction.data, data);
+    }
+
+    #[test]
+    fn governance_instructions() {
+        let payer = Pubkey::new_unique();
+        let instruction = set_fee(&payer, 1);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                AccountMeta::new_readonly(payer, true),
+                AccountMeta::new(get_config_address(), false),
+            ]
+        );
+        assert_eq!(instruction.data[..8], SET_FEE_DISCRIMINATOR);
+        assert_eq!(instruction.data[8..], 1u64.to_le_bytes());
+
+        let data_sources = [DataSource {
+            chain:   26,
+            emitter: Pubkey::new_unique(),
+        }];
+        assert_eq!(
+            set_data_sources(&payer, &data_sources).data[8..],
+            data_sources.to_vec().try_to_vec().unwrap()
+        );
+        assert_eq!(
+            accept_governance_authority_transfer(&payer).data,
+            ACCEPT_GOVERNANCE_AUTHORITY_TRANSFER_DISCRIMINATOR
+        );
+    }
+
+    #[test]
+    fn update_price_feed_instruction() {
+        let payer = Pubkey::new_unique();
+        let encoded_vaa = Pubkey::new_unique();
+        let feed_id = FeedId::new([7; 32]);
+        let params = PostUpdateParams {
+            merkle_price_update: MerklePriceUpdate {
+                message: vec![1],
+                proof:   vec![],
+            },
+            treasury_id:         2,
+        };
+        let instruction = update_price_feed(&payer, &encoded_vaa, 3, &feed_id, &params);
+
+        assert_eq!(instruction.program_id, PYTH_PUSH_ORACLE_ID);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new_readonly(crate::ID, false),
+                AccountMeta::new_readonly(encoded_vaa, false),
+                AccountMeta::new_readonly(get_config_address(), false),
+                AccountMeta::new(get_treasury_address(2), false),
+                AccountMeta::new(get_price_feed_address(3, &feed_id), false),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ]
+        );
+
+        let mut data = UPDATE_PRICE_FEED_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[1, 0, 0, 0, 1, 0, 0, 0, 0, 2]);
+        data.extend_from_slice(&[3, 0]);
+        data.extend_from_slice(&[7; 32]);
+        assert_eq!(instruction.data, data);
+    }
+}
@@ -1,9 +1,22 @@
+#[cfg(feature = "anchor")]
+use anchor_lang::prelude::{
+    account,
+    AnchorDeserialize,
+    AnchorSerialize,
+};
 use {
-    anchor_lang::prelude::*,
+    borsh::{
+        BorshDeserialize,
+        BorshSerialize,
+    },
     solana_program::pubkey::Pubkey,
 };
 
-#[account]
+#[cfg_attr(feature = "anchor", account)]
+#[cfg_attr(
+    not(feature = "anchor"),
+    derive(BorshSerialize, BorshDeserialize, Clone)
+)]
 #[derive(Debug, PartialEq)]
 pub struct Config {
     pub governance_authority:          Pubkey, // This authority can update the other fields
@@ -14,13 +27,15 @@ pub struct Config {
     pub minimum_signatures:            u8, // The minimum number of signatures required to accept a VAA
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+#[derive(BorshSerialize, BorshDeserialize, Clone, PartialEq, Debug)]
 pub struct DataSource {
     pub chain:   u16,
     pub emitter: Pubkey,
 }
 
 impl Config {
+    /// The first 8 bytes of the data of the `Config` account, derived from the name of the account like for all Anchor accounts.
+    pub const DISCRIMINATOR: [u8; 8] = [155, 12, 170, 224, 30, 250, 204, 130];
     pub const LEN: usize = 370; // This is two times the current size of a Config account with 2 data sources, to leave space for more fields
 }
 
@@ -29,10 +44,7 @@ pub mod tests {
     use {
         super::DataSource,
         crate::config::Config,
-        anchor_lang::{
-            AnchorSerialize,
-            Discriminator,
-        },
+        borsh::BorshSerialize,
         solana_program::pubkey::Pubkey,
     };
 
@@ -61,7 +73,15 @@ pub mod tests {
             32 + 1 + 32 + 32 + 4 + 1 + 33 + 1 + 33 + 8 + 1
         );
         assert!(
-            Config::discriminator().len() + test_config.try_to_vec().unwrap().len() <= Config::LEN
+            Config::DISCRIMINATOR.len() + test_config.try_to_vec().unwrap().len() <= Config::LEN
+        );
+    }
+    #[cfg(feature = "anchor")]
+    #[test]
+    fn anchor_discriminator() {
+        assert_eq!(
+            Config::DISCRIMINATOR,
+            <Config as anchor_lang::Discriminator>::DISCRIMINATOR
         );
     }
 }
@@ -0,0 +1,755 @@
+//! Cross-program invocations of the Pyth Solana Receiver program, to post and close price update accounts from another program,
+//! and of the Pyth Push Oracle program, to update its price feed accounts.
+//!
+//! Each instruction has a native wrapper calling `invoke_signed` for programs that don't use Anchor,
+//! and, with the `anchor` feature, an Anchor wrapper taking a `CpiContext`, like the ones generated by Anchor for the `cpi` module of a program.
+//! The native wrappers take the accounts of this module, and the Anchor wrappers take the typed accounts of its `accounts` module.
+// The Anchor wrappers return `anchor_lang::Result` like the CPIs generated by Anchor
+#![allow(clippy::result_large_err)]
+#[cfg(feature = "anchor")]
+use anchor_lang::prelude::{
+    CpiContext,
+    Result,
+    ToAccountInfo,
+    ToAccountInfos,
+    ToAccountMetas,
+};
+use {
+    crate::{
+        client::{
+            instruction_data,
+            update_price_feed_data,
+            PostUpdateAtomicParams,
+            PostUpdateParams,
+            POST_UPDATE_ATOMIC_DISCRIMINATOR,
+            POST_UPDATE_DISCRIMINATOR,
+            RECLAIM_RENT_DISCRIMINATOR,
+        },
+        price_update::FeedId,
+        PYTH_PUSH_ORACLE_ID,
+    },
+    solana_program::{
+        account_info::AccountInfo,
+        entrypoint::ProgramResult,
+        instruction::{
+            AccountMeta,
+            Instruction,
+        },
+        program::invoke_signed,
+        pubkey::Pubkey,
+    },
+};
+
+/// The accounts of the `post_update` instruction.
+///
+/// - `encoded_vaa` is a Wormhole encoded VAA account containing the VAA that signs the Merkle root of the price update.
+/// - `config` is the receiver's `Config` account, see [`get_config_address`](crate::pda::get_config_address).
+/// - `treasury` is the treasury account of the `treasury_id` of the instruction, see [`get_treasury_address`](crate::pda::get_treasury_address).
+/// - `price_update_account` is the `PriceUpdateV2` account to write to. It must sign, and either be a new account or an existing account whose write authority is `write_authority`.
+pub struct PostUpdate<'info> {
+    pub payer:                AccountInfo<'info>,
+    pub encoded_vaa:          AccountInfo<'info>,
+    pub config:               AccountInfo<'info>,
+    pub treasury:             AccountInfo<'info>,
+    pub price_update_account: AccountInfo<'info>,
+    pub system_program:       AccountInfo<'info>,
+    pub write_authority:      AccountInfo<'info>,
+}
+
+/// The accounts of the `post_update_atomic` instruction.
+///
+/// They are the same as for [`PostUpdate`], except for `guardian_set`, the Wormhole guardian set account that signed the VAA included in the instruction,
+/// see [`get_guardian_set_address`](crate::pda::get_guardian_set_address).
+pub struct PostUpdateAtomic<'info> {
+    pub payer:                AccountInfo<'info>,
+    pub guardian_set:         AccountInfo<'info>,
+    pub config:               AccountInfo<'info>,
+    pub treasury:             AccountInfo<'info>,
+    pub price_update_account: AccountInfo<'info>,
+    pub system_program:       AccountInfo<'info>,
+    pub write_authority:      AccountInfo<'info>,
+}
+
+/// The accounts of the `reclaim_rent` instruction, which closes `price_update_account` and returns its rent to `payer`, who must be its write authority.
+pub struct ReclaimRent<'info> {
+    pub payer:                AccountInfo<'info>,
+    pub price_update_account: AccountInfo<'info>,
+}
+
+/// The accounts of the Pyth Push Oracle's `update_price_feed` instruction.
+///
+/// - `pyth_solana_receiver` is the Pyth Solana Receiver program, which verifies the price update.
+/// - `encoded_vaa`, `config` and `treasury` are the same as for [`PostUpdate`].
+/// - `price_feed_account` is the price feed account of the shard and feed id of the instruction, see [`get_price_feed_address`](crate::pda::get_price_feed_address).
+pub struct UpdatePriceFeed<'info> {
+    pub payer:                AccountInfo<'info>,
+    pub pyth_solana_receiver: AccountInfo<'info>,
+    pub encoded_vaa:          AccountInfo<'info>,
+    pub config:               AccountInfo<'info>,
+    pub treasury:             AccountInfo<'info>,
+    pub price_feed_account:   AccountInfo<'info>,
+    pub system_program:       AccountInfo<'info>,
+}
+
+/// The accounts of an instruction, in the order of the instruction's account metas.
+trait InstructionAccounts<'info> {
+    fn account_metas(&self) -> Vec<AccountMeta>;
+    fn account_infos(&self) -> Vec<AccountInfo<'info>>;
+}
+
+/// The accounts of the Anchor wrappers, typed with the accounts defined by this crate where the instruction requires an existing account.
+///
+/// `price_update_account` is an `AccountInfo` in [`PostUpdate`](accounts::PostUpdate) and [`PostUpdateAtomic`](accounts::PostUpdateAtomic)
+/// because it may be a new account that the receiver creates, and so is `price_feed_account` in [`UpdatePriceFeed`](accounts::UpdatePriceFeed).
+#[cfg(feature = "anchor")]
+pub mod accounts {
+    use {
+        crate::{
+            config::Config,
+            price_update::PriceUpdateV2,
+        },
+        anchor_lang::prelude::{
+            Account,
+            AccountInfo,
+            Program,
+            System,
+        },
+    };
+
+    /// The accounts of the `post_update` instruction, see [`super::PostUpdate`].
+    pub struct PostUpdate<'info> {
+        pub payer:                AccountInfo<'info>,
+        pub encoded_vaa:          AccountInfo<'info>,
+        pub config:               Account<'info, Config>,
+        pub treasury:             AccountInfo<'info>,
+        pub price_update_account: AccountInfo<'info>,
+        pub system_program:       Program<'info, System>,
+        pub write_authority:      AccountInfo<'info>,
+    }
+
+    /// The accounts of the `post_update_atomic` instruction, see [`super::PostUpdateAtomic`].
+    pub struct PostUpdateAtomic<'info> {
+        pub payer:                AccountInfo<'info>,
+        pub guardian_set:         AccountInfo<'info>,
+        pub config:               Account<'info, Config>,
+        pub treasury:             AccountInfo<'info>,
+        pub price_update_account: AccountInfo<'info>,
+        pub system_program:       Program<'info, System>,
+        pub write_authority:      AccountInfo<'info>,
+    }
+
+    /// The accounts of the `reclaim_rent` instruction, see [`super::ReclaimRent`].
+    pub struct ReclaimRent<'info> {
+        pub payer:                AccountInfo<'info>,
+        pub price_update_account: Account<'info, PriceUpdateV2>,
+    }
+
+    /// The accounts of the Pyth Push Oracle's `update_price_feed` instruction, see [`super::UpdatePriceFeed`].
+    pub struct UpdatePriceFeed<'info> {
+        pub payer:                AccountInfo<'info>,
+        pub pyth_solana_receiver: AccountInfo<'info>,
+        pub encoded_vaa:          AccountInfo<'info>,
+        pub config:               Account<'info, Config>,
+        pub treasury:             AccountInfo<'info>,
+        pub price_feed_account:   AccountInfo<'info>,
+        pub system_program:       Program<'info, System>,
+    }
+}
+
+/// Implement the Anchor traits of the typed accounts of [`accounts`] through the untyped accounts of the same instruction.
+#[cfg(feature = "anchor")]
+macro_rules! impl_anchor_accounts {
+    ($($accounts:ident { $($field:ident),* }),*) => {
+        $(
+            impl<'info> From<&accounts::$accounts<'info>> for $accounts<'info> {
+                fn from(accounts: &accounts::$accounts<'info>) -> Self {
+                    $accounts {
+                        $($field: accounts.$field.to_account_info()),*
+                    }
+                }
+            }
+
+            impl ToAccountMetas for accounts::$accounts<'_> {
+                fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+                    $accounts::from(self).account_metas()
+                }
+            }
+
+            impl<'info> ToAccountInfos<'info> for accounts::$accounts<'info> {
+                fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+                    $accounts::from(self).account_infos()
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "anchor")]
+impl_anchor_accounts!(
+    PostUpdate {
+        payer,
+        encoded_vaa,
+        config,
+        treasury,
+        price_update_account,
+        system_program,
+        write_authority
+    },
+    PostUpdateAtomic {
+        payer,
+        guardian_set,
+        config,
+        treasury,
+        price_update_account,
+        system_program,
+        write_authority
+    },
+    ReclaimRent {
+        payer,
+        price_update_account
+    },
+    UpdatePriceFeed {
+        payer,
+        pyth_solana_receiver,
+        encoded_vaa,
+        config,
+        treasury,
+        price_feed_account,
+        system_program
+    }
+);
+
+impl<'info> InstructionAccounts<'info> for PostUpdate<'info> {
+    fn account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(*self.payer.key, true),
+            AccountMeta::new_readonly(*self.encoded_vaa.key, false),
+            AccountMeta::new_readonly(*self.config.key, false),
+            AccountMeta::new(*self.treasury.key, false),
+            AccountMeta::new(*self.price_update_account.key, true),
+            AccountMeta::new_readonly(*self.system_program.key, false),
+            AccountMeta::new_readonly(*self.write_authority.key, true),
+        ]
+    }
+
+    fn account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![
+            self.payer.clone(),
+            self.encoded_vaa.clone(),
+            self.config.clone(),
+            self.treasury.clone(),
+            self.price_update_account.clone(),
+            self.system_program.clone(),
+            self.write_authority.clone(),
+        ]
+    }
+}
+
+impl<'info> InstructionAccounts<'info> for PostUpdateAtomic<'info> {
+    fn account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(*self.payer.key, true),
+            AccountMeta::new_readonly(*self.guardian_set.key, false),
+            AccountMeta::new_readonly(*self.config.key, false),
+            AccountMeta::new(*self.treasury.key, false),
+            AccountMeta::new(*self.price_update_account.key, true),
+            AccountMeta::new_readonly(*self.system_program.key, false),
+            AccountMeta::new_readonly(*self.write_authority.key, true),
+        ]
+    }
+
+    fn account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![
+            self.payer.clone(),
+            self.guardian_set.clone(),
+            self.config.clone(),
+            self.treasury.clone(),
+            self.price_update_account.clone(),
+            self.system_program.clone(),
+            self.write_authority.clone(),
+        ]
+    }
+}
+
+impl<'info> InstructionAccounts<'info> for ReclaimRent<'info> {
+    fn account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(*self.payer.key, true),
+            AccountMeta::new(*self.price_update_account.key, false),
+        ]
+    }
+
+    fn account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![self.payer.clone(), self.price_update_account.clone()]
+    }
+}
+
+impl<'info> InstructionAccounts<'info> for UpdatePriceFeed<'info> {
+    fn account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(*self.payer.key, true),
+            AccountMeta::new_readonly(*self.pyth_solana_receiver.key, false),
+            AccountMeta::new_readonly(*self.encoded_vaa.key, false),
+            AccountMeta::new_readonly(*self.config.key, false),
+            AccountMeta::new(*self.treasury.key, false),
+            AccountMeta::new(*self.price_feed_account.key, false),
+            AccountMeta::new_readonly(*self.system_program.key, false),
+        ]
+    }
+
+    fn account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![
+            self.payer.clone(),
+            self.pyth_solana_receiver.clone(),
+            self.encoded_vaa.clone(),
+            self.config.clone(),
+            self.treasury.clone(),
+            self.price_feed_account.clone(),
+            self.system_program.clone(),
+        ]
+    }
+}
+
+/// Post a price update to `price_update_account` by invoking the receiver's `post_update` instruction.
+///
+/// # Example
+/// ```
+/// use pyth_solana_receiver_sdk::{client::PostUpdateParams, config::Config, cpi};
+/// use anchor_lang::prelude::*;
+///
+/// #[derive(Accounts)]
+/// pub struct PostPriceUpdate<'info> {
+///     #[account(mut)]
+///     pub payer: Signer<'info>,
+///     /// CHECK: Checked by the receiver program
+///     pub encoded_vaa: AccountInfo<'info>,
+///     pub config: Account<'info, Config>,
+///     /// CHECK: Checked by the receiver program
+///     #[account(mut)]
+///     pub treasury: AccountInfo<'info>,
+///     #[account(mut)]
+///     pub price_update_account: Signer<'info>,
+///     pub system_program: Program<'info, System>,
+///     /// CHECK: The receiver program
+///     pub pyth_solana_receiver: AccountInfo<'info>,
+/// }
+///
+/// pub fn post_price_update(ctx : Context<PostPriceUpdate>, params: PostUpdateParams) -> Result<()> {
+///     let accounts = cpi::accounts::PostUpdate {
+///         payer: ctx.accounts.payer.to_account_info(),
+///         encoded_vaa: ctx.accounts.encoded_vaa.to_account_info(),
+///         config: ctx.accounts.config.clone(),
+///         treasury: ctx.accounts.treasury.to_account_info(),
+///         price_update_account: ctx.accounts.price_update_account.to_account_info(),
+///         system_program: ctx.accounts.system_program.clone(),
+///         write_authority: ctx.accounts.payer.to_account_info(),
+///     };
+///     cpi::post_update(CpiContext::new(ctx.accounts.pyth_solana_receiver.to_account_info(), accounts), &params)?;
+///     Ok(())
+/// }
+///```
+#[cfg(feature = "anchor")]
+pub fn post_update<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::PostUpdate<'info>>,
+    params: &PostUpdateParams,
+) -> Result<()> {
+    invoke_cpi(
+        crate::ID,
+        ctx,
+        instruction_data(POST_UPDATE_DISCRIMINATOR, params),
+    )
+}
+
+/// Verify a VAA and post its price update to `price_update_account` by invoking the receiver's `post_update_atomic` instruction.
+#[cfg(feature = "anchor")]
+pub fn post_update_atomic<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::PostUpdateAtomic<'info>>,
+    params: &PostUpdateAtomicParams,
+) -> Result<()> {
+    invoke_cpi(
+        crate::ID,
+        ctx,
+        instruction_data(POST_UPDATE_ATOMIC_DISCRIMINATOR, params),
+    )
+}
+
+/// Close `price_update_account` and return its rent to `payer` by invoking the receiver's `reclaim_rent` instruction.
+#[cfg(feature = "anchor")]
+pub fn reclaim_rent<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::ReclaimRent<'info>>,
+) -> Result<()> {
+    invoke_cpi(
+        crate::ID,
+        ctx,
+        instruction_data(RECLAIM_RENT_DISCRIMINATOR, &()),
+    )
+}
+
+/// Like `post_update`, for programs that don't use Anchor. `signer_seeds` are the seeds of the PDAs among the accounts that must sign.
+pub fn invoke_post_update<'info>(
+    program: &AccountInfo<'info>,
+    accounts: &PostUpdate<'info>,
+    params: &PostUpdateParams,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    invoke_native(
+        crate::ID,
+        program,
+        accounts,
+        instruction_data(POST_UPDATE_DISCRIMINATOR, params),
+        signer_seeds,
+    )
+}
+
+/// Like `post_update_atomic`, for programs that don't use Anchor. `signer_seeds` are the seeds of the PDAs among the accounts that must sign.
+pub fn invoke_post_update_atomic<'info>(
+    program: &AccountInfo<'info>,
+    accounts: &PostUpdateAtomic<'info>,
+    params: &PostUpdateAtomicParams,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    invoke_native(
+        crate::ID,
+        program,
+        accounts,
+        instruction_data(POST_UPDATE_ATOMIC_DISCRIMINATOR, params),
+        signer_seeds,
+    )
+}
+
+/// Like `reclaim_rent`, for programs that don't use Anchor. `signer_seeds` are the seeds of the PDAs among the accounts that must sign.
+pub fn invoke_reclaim_rent<'info>(
+    program: &AccountInfo<'info>,
+    accounts: &ReclaimRent<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    invoke_native(
+        crate::ID,
+        program,
+        accounts,
+        instruction_data(RECLAIM_RENT_DISCRIMINATOR, &()),
+        signer_seeds,
+    )
+}
+
+/// Write a price update to the Pyth Push Oracle price feed account of `shard_id` and `feed_id` by invoking the push oracle's `update_price_feed` instruction.
+///
+/// The `program` of `ctx` must be the Pyth Push Oracle program.
+#[cfg(feature = "anchor")]
+pub fn update_price_feed<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, accounts::UpdatePriceFeed<'info>>,
+    params: &PostUpdateParams,
+    shard_id: u16,
+    feed_id: &FeedId,
+) -> Result<()> {
+    invoke_cpi(
+        PYTH_PUSH_ORACLE_ID,
+        ctx,
+        update_price_feed_data(params, shard_id, feed_id),
+    )
+}
+
+/// Like `update_price_feed`, for programs that don't use Anchor. `program` must be the Pyth Push Oracle program.
+/// `signer_seeds` are the seeds of the PDAs among the accounts that must sign.
+pub fn invoke_update_price_feed<'info>(
+    program: &AccountInfo<'info>,
+    accounts: &UpdatePriceFeed<'info>,
+    params: &PostUpdateParams,
+    shard_id: u16,
+    feed_id: &FeedId,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    invoke_native(
+        PYTH_PUSH_ORACLE_ID,
+        program,
+        accounts,
+        update_price_feed_data(params, shard_id, feed_id),
+        signer_seeds,
+    )
+}
+
+#[cfg(feature = "anchor")]
+fn invoke_cpi<'info, T: ToAccountMetas + ToAccountInfos<'info>>(
+    program_id: Pubkey,
+    ctx: CpiContext<'_, '_, '_, 'info, T>,
+    data: Vec<u8>,
+) -> Result<()> {
+    let instruction = Instruction {
+        program_id,
+        accounts: ctx.accounts.to_account_metas(None),
+        data,
+    };
+    invoke_signed(&instruction, &ctx.to_account_infos(), ctx.signer_seeds).map_err(Into::into)
+}
+
+fn invoke_native<'info, T: InstructionAccounts<'info>>(
+    program_id: Pubkey,
+    program: &AccountInfo<'info>,
+    accounts: &T,
+    data: Vec<u8>,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let instruction = Instruction {
+        program_id,
+        accounts: accounts.account_metas(),
+        data,
+    };
+    let mut account_infos = accounts.account_infos();
+    account_infos.push(program.clone());
+    invoke_signed(&instruction, &account_infos, signer_seeds)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use {
+        super::{
+            invoke_post_update,
+            invoke_reclaim_rent,
+            InstructionAccounts,
+            PostUpdate,
+            ReclaimRent,
+        },
+        crate::{
+            client::{
+                self,
+                MerklePriceUpdate,
+                PostUpdateParams,
+            },
+            config::Config,
+            pda::{
+                get_config_address,
+                get_treasury_address,
+            },
+            price_update::PriceUpdateV2,
+        },
+        solana_program::{
+            account_info::AccountInfo,
+            entrypoint::ProgramResult,
+            instruction::Instruction,
+            program_stubs::{
+                set_syscall_stubs,
+                SyscallStubs,
+            },
+            pubkey::Pubkey,
+            system_program,
+        },
+        std::sync::{
+            Arc,
+            Mutex,
+        },
+    };
+
+    /// Records the instructions invoked through `invoke_signed` instead of executing them.
+    struct RecordInstructions(Arc<Mutex<Vec<Instruction>>>);
+
+    impl SyscallStubs for RecordInstructions {
+        fn sol_invoke_signed(
+            &self,
+            instruction: &Instruction,
+            _account_infos: &[AccountInfo],
+            _signers_seeds: &[&[&[u8]]],
+        ) -> ProgramResult {
+            self.0.lock().unwrap().push(instruction.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn account_metas() {
+        let keys = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            get_config_address(),
+            get_treasury_address(0),
+            Pubkey::new_unique(),
+            system_program::ID,
+        ];
+        let mut lamports = [0; 6];
+        let mut data = [[0u8; 0]; 6];
+        let owner = Pubkey::default();
+        let account_infos: Vec<AccountInfo> = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, false, lamports, data, &owner, false, 0)
+            })
+            .collect();
+
+        let accounts = PostUpdate {
+            payer:                account_infos[0].clone(),
+            encoded_vaa:          account_infos[1].clone(),
+            config:               account_infos[2].clone(),
+            treasury:             account_infos[3].clone(),
+            price_update_account: account_infos[4].clone(),
+            system_program:       account_infos[5].clone(),
+            write_authority:      account_infos[0].clone(),
+        };
+        let params = PostUpdateParams {
+            merkle_price_update: MerklePriceUpdate {
+                message: vec![],
+                proof:   vec![],
+            },
+            treasury_id:         0,
+        };
+        assert_eq!(
+            accounts.account_metas(),
+            client::post_update(&keys[0], &keys[1], &keys[4], &keys[0], &params).accounts
+        );
+        assert_eq!(accounts.account_infos().len(), 7);
+
+        let accounts = ReclaimRent {
+            payer:                account_infos[0].clone(),
+            price_update_account: account_infos[4].clone(),
+        };
+        assert_eq!(
+            accounts.account_metas(),
+            client::reclaim_rent(&keys[0], &keys[4]).accounts
+        );
+    }
+
+    struct TestAccount {
+        key:      Pubkey,
+        lamports: u64,
+        data:     Vec<u8>,
+        owner:    Pubkey,
+    }
+
+    impl TestAccount {
+        fn new(key: Pubkey, data: &[u8], owner: Pubkey) -> Self {
+            TestAccount {
+                key,
+                lamports: 0,
+                data: data.to_vec(),
+                owner,
+            }
+        }
+
+        fn info(&mut self) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                false,
+                false,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+
+    #[cfg(feature = "anchor")]
+    #[test]
+    fn anchor_accounts() {
+        use {
+            super::accounts,
+            crate::{
+                price_update::FeedId,
+                test_utils::{
+                    account_data,
+                    price_update,
+                },
+            },
+            anchor_lang::prelude::{
+                Account,
+                ToAccountInfos,
+                ToAccountMetas,
+            },
+        };
+
+        let mut payer = TestAccount::new(Pubkey::new_unique(), &[], system_program::ID);
+        let price_update_key = Pubkey::new_unique();
+        let mut price_update_account = TestAccount::new(
+            price_update_key,
+            &account_data(&price_update(FeedId::new([1; 32]), 1, 0, 0)),
+            crate::ID,
+        );
+        let mut wrong_owner = TestAccount::new(
+            price_update_key,
+            &price_update_account.data.clone(),
+            system_program::ID,
+        );
+
+        let payer = payer.info();
+        let accounts = accounts::ReclaimRent {
+            payer:                payer.clone(),
+            price_update_account: Account::try_from(&price_update_account.info()).unwrap(),
+        };
+        assert_eq!(
+            accounts.to_account_metas(None),
+            client::reclaim_rent(payer.key, &price_update_key).accounts
+        );
+        assert_eq!(accounts.to_account_infos().len(), 2);
+
+        // The typed accounts check the owner and the discriminator when they are created
+        assert!(Account::<PriceUpdateV2>::try_from(&wrong_owner.info()).is_err());
+    }
+
+    #[test]
+    fn invoke() {
+        let instructions = Arc::new(Mutex::new(vec![]));
+        set_syscall_stubs(Box::new(RecordInstructions(instructions.clone())));
+
+        let mut program = TestAccount::new(crate::ID, &[], system_program::ID);
+        let mut payer = TestAccount::new(Pubkey::new_unique(), &[], system_program::ID);
+        let mut encoded_vaa = TestAccount::new(Pubkey::new_unique(), &[], system_program::ID);
+        let mut config = TestAccount::new(get_config_address(), &Config::DISCRIMINATOR, crate::ID);
+        let mut treasury = TestAccount::new(get_treasury_address(0), &[], system_program::ID);
+        let mut new_account = TestAccount::new(Pubkey::new_unique(), &[], system_program::ID);
+        let mut system = TestAccount::new(system_program::ID, &[], system_program::ID);
+        let mut price_update_account =
+            TestAccount::new(new_account.key, &PriceUpdateV2::DISCRIMINATOR, crate::ID);
+
+        let program = program.info();
+        let payer = payer.info();
+        let accounts = PostUpdate {
+            payer:                payer.clone(),
+            encoded_vaa:          encoded_vaa.info(),
+            config:               config.info(),
+            treasury:             treasury.info(),
+            price_update_account: new_account.info(),
+            system_program:       system.info(),
+            write_authority:      payer.clone(),
+        };
+        let params = PostUpdateParams {
+            merkle_price_update: MerklePriceUpdate {
+                message: vec![1, 2, 3],
+                proof:   vec![[4; 20]],
+            },
+            treasury_id:         0,
+        };
+
+        assert_eq!(
+            invoke_post_update(&program, &accounts, &params, &[]),
+            Ok(())
+        );
+        assert_eq!(
+            instructions.lock().unwrap().pop(),
+            Some(client::post_update(
+                payer.key,
+                accounts.encoded_vaa.key,
+                accounts.price_update_account.key,
+                payer.key,
+                &params
+            ))
+        );
+        let price_update_account = price_update_account.info();
+        assert_eq!(
+            invoke_reclaim_rent(
+                &program,
+                &ReclaimRent {
+                    payer:                payer.clone(),
+                    price_update_account: price_update_account.clone(),
+                },
+                &[]
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            instructions.lock().unwrap().pop(),
+            Some(client::reclaim_rent(payer.key, price_update_account.key))
+        );
+        assert!(instructions.lock().unwrap().is_empty());
+    }
+}
@@ -1,18 +1,92 @@
-use anchor_lang::error_code;
+use solana_program::program_error::ProgramError;
 
-#[error_code]
-#[derive(PartialEq)]
+/// The offset of the error codes of `GetPriceError`, the same as the one Anchor uses for the error codes of programs.
+pub const ERROR_CODE_OFFSET: u32 = 6000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u32)]
 pub enum GetPriceError {
-    #[msg("This price feed update's age exceeds the requested maximum age")]
     PriceTooOld = 10000, // Big number to avoid conflicts with the SDK user's program error codes
-    #[msg("The price feed update doesn't match the requested feed id")]
     MismatchedFeedId,
-    #[msg("This price feed update has a lower verification level than the one requested")]
     InsufficientVerificationLevel,
-    #[msg("Feed id must be 32 Bytes, that's 64 hex characters or 66 with a 0x prefix")]
     FeedIdMustBe32Bytes,
-    #[msg("Feed id contains non-hex characters")]
     FeedIdNonHexCharacter,
+    PriceOverflow,
+    NegativePrice,
+    NonPositivePrice,
+    ConfidenceTooWide,
+    PriceTooOldInSlots,
+    PriceInFuture,
+    MaximumAgeTooLarge,
+    WrongAccountOwner,
+    WrongAccountDiscriminator,
+    AccountDataTooShort,
+    InvalidAccountData,
+    WrongNumberOfAccounts,
+    NoValidPriceUpdates,
+}
+
+impl GetPriceError {
+    /// Gets the name of this [`GetPriceError`].
+    pub fn name(&self) -> String {
+        format!("{self:?}")
+    }
+
+    fn message(&self) -> &'static str {
+        match self {
+            GetPriceError::PriceTooOld => "This price feed update's age exceeds the requested maximum age",
+            GetPriceError::MismatchedFeedId => "The price feed update doesn't match the requested feed id",
+            GetPriceError::InsufficientVerificationLevel => "This price feed update has a lower verification level than the one requested",
+            GetPriceError::FeedIdMustBe32Bytes => "Feed id must be 32 Bytes, that's 64 hex characters or 66 with a 0x prefix",
+            GetPriceError::FeedIdNonHexCharacter => "Feed id contains non-hex characters",
+            GetPriceError::PriceOverflow => "Price arithmetic overflowed its integer representation",
+            GetPriceError::NegativePrice => "This price is negative and cannot be converted to an unsigned amount",
+            GetPriceError::NonPositivePrice => "This price is zero or negative",
+            GetPriceError::ConfidenceTooWide => "This price's confidence interval is wider than the requested maximum",
+            GetPriceError::PriceTooOldInSlots => "This price update was posted more slots ago than the requested maximum slot age",
+            GetPriceError::PriceInFuture => "This price feed update's publish time is further in the future than the requested maximum skew",
+            GetPriceError::MaximumAgeTooLarge => "The requested maximum age is too large, it must fit in an i64",
+            GetPriceError::WrongAccountOwner => "This account is not owned by the Pyth Solana Receiver program",
+            GetPriceError::WrongAccountDiscriminator => "This account's discriminator doesn't match a PriceUpdateV2 account",
+            GetPriceError::AccountDataTooShort => "This account's data is too short to contain a PriceUpdateV2",
+            GetPriceError::InvalidAccountData => "This account's data could not be decoded as a PriceUpdateV2",
+            GetPriceError::WrongNumberOfAccounts => "The number of price update accounts doesn't match the number of requested feed ids",
+            GetPriceError::NoValidPriceUpdates => "Not enough price updates passed the checks, or too many of them were rejected as outliers",
+        }
+    }
+}
+
+impl From<GetPriceError> for u32 {
+    fn from(error: GetPriceError) -> u32 {
+        error as u32 + ERROR_CODE_OFFSET
+    }
+}
+
+impl std::fmt::Display for GetPriceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+#[cfg(feature = "anchor")]
+impl From<GetPriceError> for anchor_lang::error::Error {
+    fn from(error: GetPriceError) -> Self {
+        anchor_lang::error::Error::from(anchor_lang::error::AnchorError {
+            error_name:        error.name(),
+            error_code_number: error.into(),
+            error_msg:         error.to_string(),
+            error_origin:      None,
+            compared_values:   None,
+        })
+    }
+}
+
+/// Allows programs that don't use Anchor to propagate a `GetPriceError` with `?`.
+/// The resulting `ProgramError::Custom` code is the same as the one of the corresponding Anchor error.
+impl From<GetPriceError> for ProgramError {
+    fn from(error: GetPriceError) -> Self {
+        ProgramError::Custom(error.into())
+    }
 }
 
 #[macro_export]
@@ -23,3 +97,40 @@ macro_rules! check {
         }
     };
 }
+
+#[cfg(test)]
+pub mod tests {
+    use {
+        super::GetPriceError,
+        solana_program::program_error::ProgramError,
+    };
+
+    #[test]
+    fn error_codes() {
+        assert_eq!(u32::from(GetPriceError::PriceTooOld), 16000);
+        assert_eq!(
+            ProgramError::from(GetPriceError::NoValidPriceUpdates),
+            ProgramError::Custom(16017)
+        );
+        assert_eq!(
+            GetPriceError::MismatchedFeedId.to_string(),
+            "The price feed update doesn't match the requested feed id"
+        );
+        assert_eq!(GetPriceError::MismatchedFeedId.name(), "MismatchedFeedId");
+    }
+
+    #[cfg(feature = "anchor")]
+    #[test]
+    fn anchor_error_codes() {
+        for error in [
+            GetPriceError::PriceTooOld,
+            GetPriceError::FeedIdNonHexCharacter,
+            GetPriceError::NoValidPriceUpdates,
+        ] {
+            assert_eq!(
+                ProgramError::from(anchor_lang::error::Error::from(error)),
+                ProgramError::from(error)
+            );
+        }
+    }
+}
@@ -0,0 +1,316 @@
+use {
+    crate::error::GetPriceError,
+    borsh::{
+        BorshDeserialize,
+        BorshSchema,
+        BorshSerialize,
+    },
+    serde::{
+        de::Error as _,
+        Deserialize,
+        Deserializer,
+        Serialize,
+        Serializer,
+    },
+    std::{
+        fmt,
+        str::FromStr,
+    },
+};
+
+/// Id of a feed producing the message. One feed produces one or more messages.
+///
+/// Price feed ids are a 32 byte unique identifier for each price feed in the Pyth network.
+/// They are usually represented as a 64 character hex string with a 0x prefix, which is how a `FeedId` is displayed
+/// and serialized with human-readable serde formats such as JSON.
+/// With borsh and binary serde formats, a `FeedId` is serialized as its 32 raw bytes.
+///
+/// # Example
+/// ```
+/// use pyth_solana_receiver_sdk::price_update::FeedId;
+///
+/// const SOL_USD: FeedId = FeedId::from_hex_const("0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d");
+///
+/// let feed_id: FeedId = "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d".parse().unwrap();
+/// assert_eq!(feed_id, SOL_USD);
+/// assert_eq!(feed_id.to_string(), "0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d");
+/// ```
+#[derive(
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Default,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub struct FeedId([u8; 32]);
+
+impl FeedId {
+    pub const fn new(bytes: [u8; 32]) -> Self {
+        FeedId(bytes)
+    }
+
+    pub const fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    pub const fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Parse a `FeedId` from a hex string, with or without a 0x prefix.
+    pub fn from_hex(input: &str) -> std::result::Result<Self, GetPriceError> {
+        parse_hex(input.as_bytes()).map(FeedId)
+    }
+
+    /// Parse a `FeedId` from a hex string, with or without a 0x prefix, in a const context.
+    ///
+    /// When used to initialize a `const`, an invalid hex string is a compile-time error.
+    ///
+    /// # Panics
+    /// Panics if the string is not a valid feed id.
+    pub const fn from_hex_const(input: &str) -> Self {
+        match parse_hex(input.as_bytes()) {
+            Ok(bytes) => FeedId(bytes),
+            Err(GetPriceError::FeedIdMustBe32Bytes) => {
+                panic!("Feed id must be 32 Bytes, that's 64 hex characters or 66 with a 0x prefix")
+            }
+            Err(_) => panic!("Feed id contains non-hex characters"),
+        }
+    }
+}
+
+/// Parse a [`FeedId`] from a hex string literal, with or without a 0x prefix, at compile time.
+///
+/// The hex string is validated when the program is compiled, so the feed id costs no compute units or error handling at runtime.
+/// An invalid string is a compile error with the same message as `GetPriceError::FeedIdMustBe32Bytes` or `GetPriceError::FeedIdNonHexCharacter`.
+///
+/// The macro expands to a `FeedId` rather than a `[u8; 32]`, so that its result can be passed to the price getters directly.
+/// Call [`FeedId::to_bytes`], which is also `const`, where the raw bytes are needed.
+///
+/// # Example
+/// ```
+/// use pyth_solana_receiver_sdk::{feed_id, price_update::FeedId};
+///
+/// const SOL_USD: FeedId = feed_id!("0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d");
+/// const SOL_USD_BYTES: [u8; 32] = SOL_USD.to_bytes();
+/// assert_eq!(SOL_USD_BYTES[0], 0xef);
+/// ```
+///
+/// ```compile_fail
+/// use pyth_solana_receiver_sdk::feed_id;
+///
+/// let feed_id = feed_id!("0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b5"); // Too short
+/// ```
+///
+/// ```compile_fail
+/// use pyth_solana_receiver_sdk::feed_id;
+///
+/// let feed_id = feed_id!("0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56z"); // Not hex
+/// ```
+#[macro_export]
+macro_rules! feed_id {
+    ($hex:expr) => {{
+        const FEED_ID: $crate::feed_id::FeedId = $crate::feed_id::FeedId::from_hex_const($hex);
+        FEED_ID
+    }};
+}
+
+/// Parse 32 bytes from a hex string of 64 characters, or 66 characters with a 0x prefix.
+const fn parse_hex(input: &[u8]) -> std::result::Result<[u8; 32], GetPriceError> {
+    let digits_start = match input.len() {
+        66 if input[0] == b'0' && (input[1] == b'x' || input[1] == b'X') => 2,
+        66 => return Err(GetPriceError::FeedIdNonHexCharacter),
+        64 => 0,
+        _ => return Err(GetPriceError::FeedIdMustBe32Bytes),
+    };
+    let mut bytes = [0; 32];
+    let mut i = 0;
+    while i < 32 {
+        let high = match hex_digit(input[digits_start + 2 * i]) {
+            Some(digit) => digit,
+            None => return Err(GetPriceError::FeedIdNonHexCharacter),
+        };
+        let low = match hex_digit(input[digits_start + 2 * i + 1]) {
+            Some(digit) => digit,
+            None => return Err(GetPriceError::FeedIdNonHexCharacter),
+        };
+        bytes[i] = (high << 4) | low;
+        i += 1;
+    }
+    Ok(bytes)
+}
+
+const fn hex_digit(character: u8) -> Option<u8> {
+    match character {
+        b'0'..=b'9' => Some(character - b'0'),
+        b'a'..=b'f' => Some(character - b'a' + 10),
+        b'A'..=b'F' => Some(character - b'A' + 10),
+        _ => None,
+    }
+}
+
+impl From<[u8; 32]> for FeedId {
+    fn from(bytes: [u8; 32]) -> Self {
+        FeedId(bytes)
+    }
+}
+
+impl From<FeedId> for [u8; 32] {
+    fn from(feed_id: FeedId) -> Self {
+        feed_id.0
+    }
+}
+
+impl AsRef<[u8]> for FeedId {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl FromStr for FeedId {
+    type Err = GetPriceError;
+
+    fn from_str(input: &str) -> std::result::Result<Self, Self::Err> {
+        FeedId::from_hex(input)
+    }
+}
+
+impl fmt::Display for FeedId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
+
+impl fmt::Debug for FeedId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FeedId({})", self)
+    }
+}
+
+/// Human-readable formats such as JSON use the hex string of the feed id, while binary formats use its 32 raw bytes,
+/// so that messages containing a `FeedId` keep the layout of the Pyth wire format.
+impl Serialize for FeedId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            Serialize::serialize(&self.0, serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for FeedId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let input = <std::borrow::Cow<str> as Deserialize>::deserialize(deserializer)?;
+            FeedId::from_hex(&input).map_err(D::Error::custom)
+        } else {
+            <[u8; 32] as Deserialize>::deserialize(deserializer).map(FeedId)
+        }
+    }
+}
+
+/// Serde helpers that always (de)serialize a `FeedId` as its 32 raw bytes, whatever the format.
+/// Pyth messages use these so that they keep the layout of the Pyth wire format, whose deserializer
+/// reports itself as human readable.
+pub mod bytes {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        feed_id: &FeedId,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        Serialize::serialize(&feed_id.0, serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<FeedId, D::Error> {
+        <[u8; 32] as Deserialize>::deserialize(deserializer).map(FeedId)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use {
+        super::FeedId,
+        crate::error::GetPriceError,
+        borsh::BorshSerialize,
+    };
+
+    const SOL_USD_HEX: &str = "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
+    const SOL_USD: FeedId = FeedId::from_hex_const(
+        "0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
+    );
+
+    #[test]
+    fn parse() {
+        let bytes: [u8; 32] = hex::decode(SOL_USD_HEX).unwrap().try_into().unwrap();
+        assert_eq!(SOL_USD, FeedId::new(bytes));
+        assert_eq!(SOL_USD_HEX.parse::<FeedId>().unwrap(), SOL_USD);
+        assert_eq!(
+            feed_id!("ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d"),
+            SOL_USD
+        );
+        assert_eq!(
+            format!("0x{}", SOL_USD_HEX).parse::<FeedId>().unwrap(),
+            SOL_USD
+        );
+        assert_eq!(
+            SOL_USD_HEX.to_uppercase().parse::<FeedId>().unwrap(),
+            SOL_USD
+        );
+
+        assert_eq!(
+            FeedId::from_hex(&SOL_USD_HEX[1..]),
+            Err(GetPriceError::FeedIdMustBe32Bytes)
+        );
+        assert_eq!(
+            FeedId::from_hex(&format!("0x{}00", SOL_USD_HEX)),
+            Err(GetPriceError::FeedIdMustBe32Bytes)
+        );
+        assert_eq!(
+            FeedId::from_hex(&format!("{}g", &SOL_USD_HEX[1..])),
+            Err(GetPriceError::FeedIdNonHexCharacter)
+        );
+        assert_eq!(
+            FeedId::from_hex(&format!("00{}", SOL_USD_HEX)),
+            Err(GetPriceError::FeedIdNonHexCharacter)
+        );
+        // Multi-byte characters don't panic
+        assert_eq!(
+            FeedId::from_hex(&format!("é{}", &SOL_USD_HEX[2..])),
+            Err(GetPriceError::FeedIdNonHexCharacter)
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(SOL_USD.to_string(), format!("0x{}", SOL_USD_HEX));
+        assert_eq!(
+            format!("{:?}", SOL_USD),
+            format!("FeedId(0x{})", SOL_USD_HEX)
+        );
+    }
+
+    #[test]
+    fn serialization() {
+        assert_eq!(
+            serde_json::to_string(&SOL_USD).unwrap(),
+            format!("\"0x{}\"", SOL_USD_HEX)
+        );
+        assert_eq!(
+            serde_json::from_str::<FeedId>(&format!("\"{}\"", SOL_USD_HEX)).unwrap(),
+            SOL_USD
+        );
+        assert!(serde_json::from_str::<FeedId>("\"0x1234\"").is_err());
+
+        assert_eq!(SOL_USD.try_to_vec().unwrap(), SOL_USD.to_bytes().to_vec());
+    }
+}
@@ -0,0 +1,110 @@
+//! Well-known Pyth price feed ids.
+//!
+//! These ids are those of the stable Pyth price feeds listed at <https://pyth.network/developers/price-feed-ids>.
+//! The same ids are used on Solana mainnet and devnet, since both clusters receive price updates from the stable Pyth network.
+//!
+//! The registry is generated by `scripts/generate_feeds.py` from `feeds/hermes_price_feeds.json`, a snapshot of the price feeds served by Hermes.
+//!
+//! # Example
+//! ```
+//! use pyth_solana_receiver_sdk::feeds;
+//!
+//! assert_eq!(feeds::feed_id_from_symbol("Crypto.SOL/USD"), Some(feeds::crypto::SOL_USD));
+//! assert_eq!(feeds::symbol_from_feed_id(&feeds::crypto::SOL_USD), Some("Crypto.SOL/USD"));
+//! ```
+use crate::feed_id::FeedId;
+
+/// The version of this registry. It is incremented whenever feeds are added, renamed or removed.
+pub const VERSION: u32 = 1;
+
+mod generated;
+
+pub use generated::{
+    crypto,
+    ALL,
+};
+
+/// Get the `FeedId` of a feed in this registry from its Pyth symbol, e.g. `Crypto.SOL/USD`. The comparison is case-insensitive.
+pub fn feed_id_from_symbol(symbol: &str) -> Option<FeedId> {
+    ALL.iter()
+        .find(|(feed_symbol, _)| feed_symbol.eq_ignore_ascii_case(symbol))
+        .map(|(_, feed_id)| *feed_id)
+}
+
+/// Get the Pyth symbol of a feed in this registry from its `FeedId`.
+pub fn symbol_from_feed_id(feed_id: &FeedId) -> Option<&'static str> {
+    ALL.iter()
+        .find(|(_, id)| id == feed_id)
+        .map(|(symbol, _)| *symbol)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use {
+        super::{
+            crypto,
+            feed_id_from_symbol,
+            symbol_from_feed_id,
+            ALL,
+        },
+        crate::price_update::get_feed_id_from_hex,
+        serde::Deserialize,
+        std::collections::BTreeSet,
+    };
+
+    #[derive(Deserialize)]
+    struct HermesPriceFeed {
+        id:         String,
+        attributes: HermesAttributes,
+    }
+
+    #[derive(Deserialize)]
+    struct HermesAttributes {
+        symbol: String,
+    }
+
+    #[test]
+    fn matches_snapshot() {
+        let snapshot: Vec<HermesPriceFeed> =
+            serde_json::from_str(include_str!("../feeds/hermes_price_feeds.json")).unwrap();
+        let snapshot: Vec<_> = snapshot
+            .iter()
+            .map(|feed| {
+                (
+                    feed.attributes.symbol.as_str(),
+                    get_feed_id_from_hex(&feed.id).unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(snapshot, ALL);
+    }
+
+    #[test]
+    fn lookup() {
+        for (symbol, feed_id) in ALL {
+            assert_eq!(feed_id_from_symbol(symbol), Some(*feed_id));
+            assert_eq!(symbol_from_feed_id(feed_id), Some(*symbol));
+        }
+        assert_eq!(feed_id_from_symbol("crypto.sol/usd"), Some(crypto::SOL_USD));
+        assert_eq!(
+            crypto::SOL_USD,
+            get_feed_id_from_hex(
+                "0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d"
+            )
+            .unwrap()
+        );
+        assert_eq!(feed_id_from_symbol("Crypto.SOL/EUR"), None);
+        assert_eq!(symbol_from_feed_id(&Default::default()), None);
+    }
+
+    #[test]
+    fn unique() {
+        let symbols: BTreeSet<_> = ALL
+            .iter()
+            .map(|(symbol, _)| symbol.to_lowercase())
+            .collect();
+        let feed_ids: BTreeSet<_> = ALL.iter().map(|(_, feed_id)| *feed_id).collect();
+        assert_eq!(symbols.len(), ALL.len());
+        assert_eq!(feed_ids.len(), ALL.len());
+    }
+}
@@ -0,0 +1,44 @@
+// @generated by scripts/generate_feeds.py from feeds/hermes_price_feeds.json, do not edit.
+use crate::feed_id::FeedId;
+
+pub mod crypto {
+    use crate::{
+        feed_id,
+        feed_id::FeedId,
+    };
+
+    pub const BTC_USD: FeedId =
+        feed_id!("0xe62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43");
+    pub const ETH_USD: FeedId =
+        feed_id!("0xff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace");
+    pub const SOL_USD: FeedId =
+        feed_id!("0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d");
+    pub const USDC_USD: FeedId =
+        feed_id!("0xeaa020c61cc479712813461ce153894a96a6c00b21ed0cfc2798d1f9a9e9c94a");
+    pub const USDT_USD: FeedId =
+        feed_id!("0x2b89b9dc8fdf9f34709a5b106b472f0f39bb6ca9ce04b0fd7f2e971688e2e53b");
+    pub const MSOL_USD: FeedId =
+        feed_id!("0xc2289a6a43d2ce91c6f55caec370f4acc38a2ed477f58813334c6d03749ff2a4");
+    pub const JITOSOL_USD: FeedId =
+        feed_id!("0x67be9f519b95cf24338801051f9a808eff0a578ccb388db73b7f6fe1de019ffb");
+    pub const BONK_USD: FeedId =
+        feed_id!("0x72b021217ca3fe68922a19aaf990109cb9d84e9ad004b4d2025ad6f529314419");
+    pub const JUP_USD: FeedId =
+        feed_id!("0x0a0408d619e9380abad35060f9192039ed5042fa6f82301d0e48bb52be830996");
+    pub const PYTH_USD: FeedId =
+        feed_id!("0x0bbf28e9a841a1cc788f6a361b17ca072d0ea3098a1e5df1c3922d06719579ff");
+}
+
+/// All the feeds in this registry, as pairs of Pyth symbol and `FeedId`.
+pub const ALL: &[(&str, FeedId)] = &[
+    ("Crypto.BTC/USD", crypto::BTC_USD),
+    ("Crypto.ETH/USD", crypto::ETH_USD),
+    ("Crypto.SOL/USD", crypto::SOL_USD),
+    ("Crypto.USDC/USD", crypto::USDC_USD),
+    ("Crypto.USDT/USD", crypto::USDT_USD),
+    ("Crypto.MSOL/USD", crypto::MSOL_USD),
+    ("Crypto.JITOSOL/USD", crypto::JITOSOL_USD),
+    ("Crypto.BONK/USD", crypto::BONK_USD),
+    ("Crypto.JUP/USD", crypto::JUP_USD),
+    ("Crypto.PYTH/USD", crypto::PYTH_USD),
+];
@@ -1,15 +1,29 @@
-use {
-    anchor_lang::declare_id,
-    solana_program::{
-        pubkey,
-        pubkey::Pubkey,
-    },
+use solana_program::{
+    declare_id,
+    pubkey::Pubkey,
 };
 
+#[cfg(feature = "offchain")]
+pub mod accumulator;
+pub mod aggregation;
+pub mod batch;
+pub mod client;
 pub mod config;
+pub mod cpi;
 pub mod error;
+pub mod feed_id;
+#[cfg(feature = "feeds")]
+pub mod feeds;
+mod math;
+#[cfg(feature = "offchain")]
+pub mod offchain;
+pub mod pda;
 pub mod price_update;
+pub mod price_update_view;
+#[cfg(test)]
+pub(crate) mod test_utils;
 
 declare_id!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
 
-pub const PYTH_PUSH_ORACLE_ID: Pubkey = pubkey!("pythWSnswVUd12oZpeFP8e9CVaEqJg25g1Vtc2biRsT");
+pub const PYTH_PUSH_ORACLE_ID: Pubkey =
+    solana_program::pubkey!("pythWSnswVUd12oZpeFP8e9CVaEqJg25g1Vtc2biRsT");
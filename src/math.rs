@@ -0,0 +1,84 @@
+use crate::price_update::Rounding;
+
+/// `10^exponent`, or `None` if it doesn't fit in a `u128`.
+fn pow10(exponent: u32) -> Option<u128> {
+    10u128.checked_pow(exponent)
+}
+
+/// Divide `numerator` by a strictly positive `denominator` with the given rounding.
+fn div_round(numerator: i128, denominator: i128, rounding: Rounding) -> i128 {
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+    match rounding {
+        Rounding::Down if remainder < 0 => quotient - 1,
+        Rounding::Up if remainder > 0 => quotient + 1,
+        Rounding::Nearest if remainder.unsigned_abs() * 2 >= denominator.unsigned_abs() => {
+            quotient + numerator.signum()
+        }
+        _ => quotient,
+    }
+}
+
+/// Like [`div_round`], but the `denominator` may be larger than `i128::MAX`.
+/// A `denominator` of `None` stands for a value too large to fit in a `u128`.
+fn div_round_wide(numerator: i128, denominator: Option<u128>, rounding: Rounding) -> i128 {
+    match denominator.and_then(|denominator| i128::try_from(denominator).ok()) {
+        Some(denominator) => div_round(numerator, denominator, rounding),
+        // The denominator is larger than the numerator, so the quotient is 0 and only the rounding matters
+        None => {
+            let round_away_from_zero = match rounding {
+                Rounding::Down => numerator < 0,
+                Rounding::Up => numerator > 0,
+                Rounding::Nearest => denominator.is_some_and(|denominator| {
+                    numerator.unsigned_abs() >= denominator - denominator / 2
+                }),
+            };
+            if round_away_from_zero {
+                numerator.signum()
+            } else {
+                0
+            }
+        }
+    }
+}
+
+/// Compute `numerator * 10^exponent / denominator` with the given rounding, for a strictly positive `denominator`.
+/// Returns `None` on overflow.
+pub(crate) fn mul_pow10_div(
+    numerator: i128,
+    exponent: i64,
+    denominator: i128,
+    rounding: Rounding,
+) -> Option<i128> {
+    if exponent >= 0 {
+        if numerator == 0 {
+            return Some(0);
+        }
+        let multiplier = i128::try_from(pow10(u32::try_from(exponent).ok()?)?).ok()?;
+        Some(div_round(
+            numerator.checked_mul(multiplier)?,
+            denominator,
+            rounding,
+        ))
+    } else {
+        let divisor = pow10(u32::try_from(-exponent).unwrap_or(u32::MAX))
+            .and_then(|divisor| divisor.checked_mul(denominator.unsigned_abs()));
+        Some(div_round_wide(numerator, divisor, rounding))
+    }
+}
+
+/// Rescale `value * 10^from_exponent` to be expressed as a multiple of `10^to_exponent`.
+/// Returns `None` on overflow.
+pub(crate) fn rescale(
+    value: i128,
+    from_exponent: i32,
+    to_exponent: i32,
+    rounding: Rounding,
+) -> Option<i128> {
+    mul_pow10_div(
+        value,
+        i64::from(from_exponent) - i64::from(to_exponent),
+        1,
+        rounding,
+    )
+}
@@ -0,0 +1,337 @@
+//! Human-readable representations of a [`Price`], for off-chain clients such as keepers and dashboards.
+//!
+//! A `Price` is displayed as its price and confidence interval with as many decimal places as its exponent, e.g. `142.37810000 ± 0.07120000` for an exponent of `-8`.
+//! Prices with a positive exponent or more than 32 decimal places are displayed in scientific notation, e.g. `15e3 ± 1e3`.
+//! For exponents between -255 and 255, this representation is lossless: [`Price::from_decimal_str`] parses it back into the same `price`, `conf` and `exponent`.
+use {
+    crate::{
+        error::GetPriceError,
+        price_update::Price,
+    },
+    rust_decimal::Decimal,
+    std::fmt,
+};
+
+const PLUS_MINUS: &str = "±";
+// Beyond this many decimal places, prices are displayed in scientific notation
+const MAX_DISPLAYED_DECIMALS: usize = 32;
+// Parsed exponents must be in `-MAX_EXPONENT..=MAX_EXPONENT`
+const MAX_EXPONENT: u32 = 255;
+
+/// The error returned when parsing a `Price` from a string fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParsePriceError {
+    /// The string isn't of the form `<price> ± <conf>`.
+    MissingConfidence,
+    /// The price or the confidence interval isn't a valid number.
+    InvalidNumber,
+    /// The price and the confidence interval don't have the same number of decimal places or the same exponent.
+    MismatchedExponents,
+    /// The confidence interval is negative.
+    NegativeConfidence,
+    /// The price or the confidence interval doesn't fit in its integer type, or the exponent is not between -255 and 255.
+    Overflow,
+}
+
+impl fmt::Display for ParsePriceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ParsePriceError::MissingConfidence => "Expected a price of the form `<price> ± <conf>`",
+            ParsePriceError::InvalidNumber => "The price or confidence interval isn't a number",
+            ParsePriceError::MismatchedExponents => {
+                "The price and confidence interval must have the same number of decimal places"
+            }
+            ParsePriceError::NegativeConfidence => "The confidence interval is negative",
+            ParsePriceError::Overflow => {
+                "The price, confidence interval or exponent is out of range"
+            }
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for ParsePriceError {}
+
+impl Price {
+    /// Get the price as a floating point number, i.e. `price * 10^exponent` rounded to the nearest `f64`.
+    pub fn to_f64(&self) -> f64 {
+        to_f64(self.price.into(), self.exponent)
+    }
+
+    /// Get the confidence interval as a floating point number, i.e. `conf * 10^exponent` rounded to the nearest `f64`.
+    pub fn conf_to_f64(&self) -> f64 {
+        to_f64(self.conf.into(), self.exponent)
+    }
+
+    /// Get the price as a `Decimal`, i.e. `price * 10^exponent`.
+    ///
+    /// Returns `GetPriceError::PriceOverflow` if the price can't be represented exactly by a `Decimal`, which has at most 28 decimal places.
+    pub fn to_decimal(&self) -> std::result::Result<Decimal, GetPriceError> {
+        to_decimal(self.price.into(), self.exponent)
+    }
+
+    /// Get the confidence interval as a `Decimal`, i.e. `conf * 10^exponent`.
+    ///
+    /// Returns `GetPriceError::PriceOverflow` if the confidence interval can't be represented exactly by a `Decimal`, which has at most 28 decimal places.
+    pub fn conf_to_decimal(&self) -> std::result::Result<Decimal, GetPriceError> {
+        to_decimal(self.conf.into(), self.exponent)
+    }
+
+    /// Parse a `Price` from its `Display` representation, such as `142.37810000 ± 0.07120000`.
+    /// `+/-` is also accepted instead of `±`.
+    ///
+    /// The exponent is the opposite of the number of decimal places, which must be the same for the price and the confidence interval,
+    /// or the exponent of the scientific notation. The exponent must be between -255 and 255, otherwise `ParsePriceError::Overflow` is returned.
+    /// The string doesn't contain a publish time, so it must be provided.
+    ///
+    /// # Example
+    /// ```
+    /// use pyth_solana_receiver_sdk::price_update::Price;
+    ///
+    /// let price = Price::from_decimal_str("142.37810000 ± 0.07120000", 100).unwrap();
+    /// assert_eq!(price, Price { price: 14237810000, conf: 7120000, exponent: -8, publish_time: 100 });
+    /// assert_eq!(price.to_string(), "142.37810000 ± 0.07120000");
+    /// ```
+    pub fn from_decimal_str(
+        input: &str,
+        publish_time: i64,
+    ) -> std::result::Result<Price, ParsePriceError> {
+        let (price, conf) = input
+            .split_once(PLUS_MINUS)
+            .or_else(|| input.split_once("+/-"))
+            .ok_or(ParsePriceError::MissingConfidence)?;
+        let (price, price_exponent) = parse_number(price.trim())?;
+        let (conf, conf_exponent) = parse_number(conf.trim())?;
+        if price_exponent != conf_exponent {
+            return Err(ParsePriceError::MismatchedExponents);
+        }
+        if price_exponent.unsigned_abs() > MAX_EXPONENT {
+            return Err(ParsePriceError::Overflow);
+        }
+        if conf < 0 {
+            return Err(ParsePriceError::NegativeConfidence);
+        }
+        Ok(Price {
+            price: i64::try_from(price).map_err(|_| ParsePriceError::Overflow)?,
+            conf: u64::try_from(conf).map_err(|_| ParsePriceError::Overflow)?,
+            exponent: price_exponent,
+            publish_time,
+        })
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            format_number(self.price.into(), self.exponent),
+            PLUS_MINUS,
+            format_number(self.conf.into(), self.exponent)
+        )
+    }
+}
+
+fn to_f64(mantissa: i128, exponent: i32) -> f64 {
+    // Parsing the scientific notation gives the correctly rounded `f64`, unlike multiplying by a power of 10
+    format!("{}e{}", mantissa, exponent)
+        .parse()
+        .unwrap_or(f64::NAN)
+}
+
+fn to_decimal(mantissa: i128, exponent: i32) -> std::result::Result<Decimal, GetPriceError> {
+    if exponent <= 0 {
+        let scale = exponent.unsigned_abs();
+        Decimal::try_from_i128_with_scale(mantissa, scale).map_err(|_| GetPriceError::PriceOverflow)
+    } else {
+        10i128
+            .checked_pow(exponent.unsigned_abs())
+            .and_then(|multiplier| mantissa.checked_mul(multiplier))
+            .and_then(|value| Decimal::try_from_i128_with_scale(value, 0).ok())
+            .ok_or(GetPriceError::PriceOverflow)
+    }
+}
+
+/// Format `mantissa * 10^exponent` with `-exponent` decimal places, or in scientific notation if `exponent` is positive
+/// or if there would be more than `MAX_DISPLAYED_DECIMALS` decimal places.
+fn format_number(mantissa: i128, exponent: i32) -> String {
+    let decimals = exponent.unsigned_abs() as usize;
+    if exponent > 0 || decimals > MAX_DISPLAYED_DECIMALS {
+        return format!("{}e{}", mantissa, exponent);
+    }
+    if decimals == 0 {
+        return mantissa.to_string();
+    }
+    let sign = if mantissa < 0 { "-" } else { "" };
+    let digits = format!("{:0>width$}", mantissa.unsigned_abs(), width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    format!("{}{}.{}", sign, integer, fraction)
+}
+
+/// Parse a number formatted by [`format_number`] into its mantissa and exponent.
+fn parse_number(input: &str) -> std::result::Result<(i128, i32), ParsePriceError> {
+    if let Some((mantissa, exponent)) = input.split_once(['e', 'E']) {
+        let exponent: i32 = exponent
+            .parse()
+            .map_err(|_| ParsePriceError::InvalidNumber)?;
+        let (mantissa, mantissa_exponent) = parse_number(mantissa)?;
+        return Ok((
+            mantissa,
+            mantissa_exponent
+                .checked_add(exponent)
+                .ok_or(ParsePriceError::Overflow)?,
+        ));
+    }
+
+    let (integer, fraction) = input.split_once('.').unwrap_or((input, ""));
+    let (negative, integer) = match integer.strip_prefix('-') {
+        Some(integer) => (true, integer),
+        None => (false, integer),
+    };
+    let digits = format!("{}{}", integer, fraction);
+    if digits.is_empty() || !digits.bytes().all(|digit| digit.is_ascii_digit()) {
+        return Err(ParsePriceError::InvalidNumber);
+    }
+    let magnitude: i128 = digits.parse().map_err(|_| ParsePriceError::Overflow)?;
+    let exponent = i32::try_from(fraction.len()).map_err(|_| ParsePriceError::Overflow)?;
+    Ok((if negative { -magnitude } else { magnitude }, -exponent))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use {
+        super::ParsePriceError,
+        crate::{
+            error::GetPriceError,
+            price_update::Price,
+        },
+        rust_decimal::Decimal,
+        std::str::FromStr,
+    };
+
+    fn price(price: i64, conf: u64, exponent: i32) -> Price {
+        Price {
+            price,
+            conf,
+            exponent,
+            publish_time: 0,
+        }
+    }
+
+    #[test]
+    fn to_f64() {
+        assert_eq!(price(14237810000, 7120000, -8).to_f64(), 142.3781);
+        assert_eq!(price(14237810000, 7120000, -8).conf_to_f64(), 0.0712);
+        assert_eq!(price(-15, 0, 3).to_f64(), -15000.0);
+        assert_eq!(price(1, 0, i32::MIN).to_f64(), 0.0);
+    }
+
+    #[test]
+    fn to_decimal() {
+        let p = price(14237810000, 7120000, -8);
+        assert_eq!(
+            p.to_decimal().unwrap(),
+            Decimal::from_str("142.3781").unwrap()
+        );
+        assert_eq!(
+            p.conf_to_decimal().unwrap(),
+            Decimal::from_str("0.0712").unwrap()
+        );
+        assert_eq!(
+            price(-15, 0, 3).to_decimal().unwrap(),
+            Decimal::from(-15000)
+        );
+        assert_eq!(
+            price(1, 0, -29).to_decimal(),
+            Err(GetPriceError::PriceOverflow)
+        );
+        assert_eq!(
+            price(i64::MAX, 0, 20).to_decimal(),
+            Err(GetPriceError::PriceOverflow)
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            price(14237810000, 7120000, -8).to_string(),
+            "142.37810000 ± 0.07120000"
+        );
+        assert_eq!(price(-5, 12, -2).to_string(), "-0.05 ± 0.12");
+        assert_eq!(price(142, 1, 0).to_string(), "142 ± 1");
+        assert_eq!(price(15, 1, 3).to_string(), "15e3 ± 1e3");
+        assert_eq!(
+            price(5, 1, -32).to_string(),
+            "0.00000000000000000000000000000005 ± 0.00000000000000000000000000000001"
+        );
+        assert_eq!(price(-5, 1, -33).to_string(), "-5e-33 ± 1e-33");
+        assert_eq!(
+            price(1, 0, i32::MIN).to_string(),
+            "1e-2147483648 ± 0e-2147483648"
+        );
+    }
+
+    #[test]
+    fn parse() {
+        for p in [
+            price(14237810000, 7120000, -8),
+            price(-5, 12, -2),
+            price(142, 1, 0),
+            price(15, 1, 3),
+            price(i64::MIN, u64::MAX, -20),
+            price(-5, 1, -33),
+            price(7, 0, -255),
+            price(7, 0, 255),
+        ] {
+            assert_eq!(Price::from_decimal_str(&p.to_string(), 0), Ok(p));
+        }
+        assert_eq!(
+            Price::from_decimal_str("142.3781 +/- 0.0712", 100),
+            Ok(Price {
+                publish_time: 100,
+                ..price(1423781, 712, -4)
+            })
+        );
+
+        assert_eq!(
+            Price::from_decimal_str("142.3781", 0),
+            Err(ParsePriceError::MissingConfidence)
+        );
+        assert_eq!(
+            Price::from_decimal_str("142.3781 ± 0.07", 0),
+            Err(ParsePriceError::MismatchedExponents)
+        );
+        assert_eq!(
+            Price::from_decimal_str("142.3781 ± -0.0712", 0),
+            Err(ParsePriceError::NegativeConfidence)
+        );
+        assert_eq!(
+            Price::from_decimal_str("1a2 ± 1", 0),
+            Err(ParsePriceError::InvalidNumber)
+        );
+        assert_eq!(
+            Price::from_decimal_str(" ± 1", 0),
+            Err(ParsePriceError::InvalidNumber)
+        );
+        assert_eq!(
+            Price::from_decimal_str("9223372036854775808 ± 1", 0),
+            Err(ParsePriceError::Overflow)
+        );
+        assert_eq!(
+            Price::from_decimal_str("1e-2000000000 ± 1e-2000000000", 0),
+            Err(ParsePriceError::Overflow)
+        );
+        assert_eq!(
+            Price::from_decimal_str("1e256 ± 1e256", 0),
+            Err(ParsePriceError::Overflow)
+        );
+        assert_eq!(
+            Price::from_decimal_str(&format!("0.{:0>256} ± 0", 1), 0),
+            Err(ParsePriceError::MismatchedExponents)
+        );
+        assert_eq!(
+            Price::from_decimal_str(&format!("0.{0:0>256} ± 0.{0:0>256}", 1), 0),
+            Err(ParsePriceError::Overflow)
+        );
+    }
+}
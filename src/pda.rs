@@ -0,0 +1,121 @@
+use {
+    crate::{
+        price_update::FeedId,
+        PYTH_PUSH_ORACLE_ID,
+    },
+    solana_program::pubkey::Pubkey,
+};
+
+/// Seed of the Pyth Solana Receiver program's `Config` account.
+pub const CONFIG_SEED: &str = "config";
+/// Seed prefix of the Pyth Solana Receiver program's treasury accounts, which collect the update fees.
+pub const TREASURY_SEED: &str = "treasury";
+/// Seed prefix of the Wormhole guardian set accounts.
+pub const GUARDIAN_SET_SEED: &str = "GuardianSet";
+/// The treasury used by default to pay update fees. Other treasuries exist to spread write locks across accounts.
+pub const DEFAULT_TREASURY_ID: u8 = 0;
+
+/// Get the address of the price feed account maintained by the Pyth Push Oracle program for a given shard and `FeedId`, along with its bump seed.
+///
+/// Price feed accounts are PDAs of the Pyth Push Oracle program with seeds `[shard_id (little-endian), feed_id]`.
+/// Unlike price update accounts posted by arbitrary users, there is a single price feed account per shard and feed.
+pub fn find_price_feed_address(shard_id: u16, feed_id: &FeedId) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[&shard_id.to_le_bytes(), feed_id.as_ref()],
+        &PYTH_PUSH_ORACLE_ID,
+    )
+}
+
+/// Get the address of the price feed account maintained by the Pyth Push Oracle program for a given shard and `FeedId`.
+///
+/// This can be used in an Anchor constraint to require that a price update account is the canonical push oracle price feed account.
+///
+/// # Example
+#[cfg_attr(feature = "anchor", doc = "```")]
+#[cfg_attr(not(feature = "anchor"), doc = "```ignore")]
+/// use pyth_solana_receiver_sdk::{pda::get_price_feed_address, price_update::{get_feed_id_from_hex, PriceUpdateV2}};
+/// use anchor_lang::prelude::*;
+///
+/// const FEED_ID: &str = "0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d"; // SOL/USD
+///
+/// #[derive(Accounts)]
+/// pub struct ReadPriceAccount<'info> {
+///     #[account(address = get_price_feed_address(0, &get_feed_id_from_hex(FEED_ID)?))]
+///     pub price_feed: Account<'info, PriceUpdateV2>,
+/// }
+/// ```
+pub fn get_price_feed_address(shard_id: u16, feed_id: &FeedId) -> Pubkey {
+    find_price_feed_address(shard_id, feed_id).0
+}
+
+/// Get the address of the `Config` account of the Pyth Solana Receiver program.
+pub fn get_config_address() -> Pubkey {
+    Pubkey::find_program_address(&[CONFIG_SEED.as_ref()], &crate::ID).0
+}
+
+/// Get the address of the treasury account of the Pyth Solana Receiver program with the given `treasury_id`.
+pub fn get_treasury_address(treasury_id: u8) -> Pubkey {
+    Pubkey::find_program_address(&[TREASURY_SEED.as_ref(), &[treasury_id]], &crate::ID).0
+}
+
+/// Get the address of a Wormhole guardian set account, given the Wormhole program from the receiver's `Config` and the guardian set index from the VAA.
+pub fn get_guardian_set_address(wormhole: &Pubkey, guardian_set_index: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            GUARDIAN_SET_SEED.as_ref(),
+            &guardian_set_index.to_be_bytes(),
+        ],
+        wormhole,
+    )
+    .0
+}
+
+#[cfg(test)]
+pub mod tests {
+    use {
+        super::{
+            find_price_feed_address,
+            get_config_address,
+            get_price_feed_address,
+        },
+        crate::{
+            price_update::get_feed_id_from_hex,
+            PYTH_PUSH_ORACLE_ID,
+        },
+        solana_program::{
+            pubkey,
+            pubkey::Pubkey,
+        },
+    };
+
+    #[test]
+    fn price_feed_address() {
+        let feed_id = get_feed_id_from_hex(
+            "0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
+        )
+        .unwrap();
+        assert_eq!(
+            get_price_feed_address(0, &feed_id),
+            pubkey!("7UVimffxr9ow1uXYxsr4LHAcV58mLzhmwaeKvJ1pjLiE")
+        );
+
+        let (address, bump) = find_price_feed_address(1, &feed_id);
+        assert_eq!(
+            Pubkey::create_program_address(
+                &[&1u16.to_le_bytes(), feed_id.as_ref(), &[bump]],
+                &PYTH_PUSH_ORACLE_ID
+            )
+            .unwrap(),
+            address
+        );
+        assert_ne!(address, get_price_feed_address(0, &feed_id));
+    }
+
+    #[test]
+    fn config_address() {
+        assert_eq!(
+            get_config_address(),
+            pubkey!("DaWUKXCyXsnzcvLUyeJRWou8KTn7XtadgTsdhJ6RHS7b")
+        );
+    }
+}
@@ -1,23 +1,33 @@
+pub use crate::feed_id::FeedId;
+#[cfg(feature = "anchor")]
+use anchor_lang::prelude::{
+    account,
+    AnchorDeserialize,
+    AnchorSerialize,
+};
 #[cfg(feature = "quickcheck")]
 use quickcheck::Arbitrary;
 use {
     crate::{
         check,
         error::GetPriceError,
-    },
-    anchor_lang::prelude::{
-        borsh::BorshSchema,
-        *,
+        math,
+        price_update_view::PriceUpdateV2View,
     },
     borsh::{
         BorshDeserialize,
+        BorshSchema,
         BorshSerialize,
     },
     serde::{
         Deserialize,
         Serialize,
     },
-    solana_program::pubkey::Pubkey,
+    solana_program::{
+        account_info::AccountInfo,
+        clock::Clock,
+        pubkey::Pubkey,
+    },
 };
 
 
@@ -32,9 +42,15 @@ use {
 ///
 /// # Warning
 /// Using partially verified price updates is dangerous, as it lowers the threshold of guardians that need to collude to produce a malicious price update.
-#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, BorshSchema, Debug)]
+///
+/// `VerificationLevel` is totally ordered, consistently with [`VerificationLevel::gte`].
+#[derive(BorshSerialize, BorshDeserialize, Copy, Clone, PartialEq, Eq, BorshSchema, Debug)]
 pub enum VerificationLevel {
-    Partial { num_signatures: u8 },
+    Partial {
+        // `BorshSchema` copies this field into a helper struct that never reads it
+        #[allow(dead_code)]
+        num_signatures: u8,
+    },
     Full,
 }
 
@@ -42,14 +58,57 @@ impl VerificationLevel {
     /// Compare two `VerificationLevel`.
     /// `Full` is always greater than `Partial`, and `Partial` with more signatures is greater than `Partial` with fewer signatures.
     pub fn gte(&self, other: VerificationLevel) -> bool {
+        *self >= other
+    }
+
+    /// The number of guardian signatures needed to fully verify a price update with a guardian set of `guardian_set_size` guardians.
+    /// Like Wormhole, this is more than two thirds of the guardians.
+    pub const fn quorum(guardian_set_size: u8) -> u8 {
+        ((guardian_set_size as u16) * 2 / 3 + 1) as u8
+    }
+
+    /// The `VerificationLevel` of a price update whose VAA has `num_signatures` verified signatures from a guardian set of `guardian_set_size` guardians.
+    /// It's `Full` if `num_signatures` reaches the [`VerificationLevel::quorum`] of the guardian set, and `Partial` otherwise.
+    pub const fn from_signatures(num_signatures: u8, guardian_set_size: u8) -> Self {
+        if num_signatures >= Self::quorum(guardian_set_size) {
+            VerificationLevel::Full
+        } else {
+            VerificationLevel::Partial { num_signatures }
+        }
+    }
+
+    /// Whether this level meets a threshold of `minimum_signatures` verified signatures, such as [`Config::minimum_signatures`](crate::config::Config::minimum_signatures).
+    /// `Full` always meets the threshold.
+    pub const fn meets_minimum_signatures(&self, minimum_signatures: u8) -> bool {
         match self {
             VerificationLevel::Full => true,
-            VerificationLevel::Partial { num_signatures } => match other {
-                VerificationLevel::Full => false,
+            VerificationLevel::Partial { num_signatures } => *num_signatures >= minimum_signatures,
+        }
+    }
+}
+
+impl PartialOrd for VerificationLevel {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for VerificationLevel {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (self, other) {
+            (VerificationLevel::Full, VerificationLevel::Full) => std::cmp::Ordering::Equal,
+            (VerificationLevel::Full, VerificationLevel::Partial { .. }) => {
+                std::cmp::Ordering::Greater
+            }
+            (VerificationLevel::Partial { .. }, VerificationLevel::Full) => {
+                std::cmp::Ordering::Less
+            }
+            (
+                VerificationLevel::Partial { num_signatures },
                 VerificationLevel::Partial {
                     num_signatures: other_num_signatures,
-                } => *num_signatures >= other_num_signatures,
-            },
+                },
+            ) => num_signatures.cmp(other_num_signatures),
         }
     }
 }
@@ -60,7 +119,11 @@ impl VerificationLevel {
 /// - `verification_level`: The [`VerificationLevel`] of this price update. This represents how many Wormhole guardian signatures have been verified for this price update.
 /// - `price_message`: The actual price update.
 /// - `posted_slot`: The slot at which this price update was posted.
-#[account]
+#[cfg_attr(feature = "anchor", account)]
+#[cfg_attr(
+    not(feature = "anchor"),
+    derive(BorshSerialize, BorshDeserialize, Clone)
+)]
 #[derive(BorshSchema)]
 pub struct PriceUpdateV2 {
     pub write_authority:    Pubkey,
@@ -70,9 +133,165 @@ pub struct PriceUpdateV2 {
 }
 
 impl PriceUpdateV2 {
+    /// The first 8 bytes of the data of `PriceUpdateV2` accounts, derived from the name of the account like for all Anchor accounts.
+    pub const DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
     pub const LEN: usize = 8 + 32 + 2 + 32 + 8 + 8 + 4 + 8 + 8 + 8 + 8 + 8;
+
+    /// Deserialize a `PriceUpdateV2` from an account owned by the Pyth Solana Receiver program, without relying on Anchor's `Account` type.
+    ///
+    /// This checks that:
+    /// - The account is owned by the Pyth Solana Receiver program, otherwise returns `GetPriceError::WrongAccountOwner`
+    /// - The account data is a `PriceUpdateV2`, see [`PriceUpdateV2::try_from_account_data`]
+    ///
+    /// # Example
+    /// ```
+    /// use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2};
+    /// use solana_program::{account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, sysvar::Sysvar};
+    ///
+    /// const MAXIMUM_AGE : u64 = 30;
+    /// const FEED_ID: &str = "0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d"; // SOL/USD
+    ///
+    /// pub fn read_price_account(price_update_account: &AccountInfo) -> ProgramResult {
+    ///     let price_update = PriceUpdateV2::try_from_account_info(price_update_account)?;
+    ///     let price = price_update.get_price_no_older_than(&Clock::get()?, MAXIMUM_AGE, &get_feed_id_from_hex(FEED_ID)?)?;
+    ///     Ok(())
+    /// }
+    ///```
+    pub fn try_from_account_info(
+        account_info: &AccountInfo,
+    ) -> std::result::Result<PriceUpdateV2, GetPriceError> {
+        check!(
+            *account_info.owner == crate::ID,
+            GetPriceError::WrongAccountOwner
+        );
+        let data = account_info
+            .try_borrow_data()
+            .map_err(|_| GetPriceError::InvalidAccountData)?;
+        PriceUpdateV2::try_from_account_data(&data)
+    }
+
+    /// Deserialize a `PriceUpdateV2` from raw account data, checking its 8-byte discriminator.
+    ///
+    /// # Warning
+    /// This function does not check the owner of the account. Use [`PriceUpdateV2::try_from_account_info`] to read accounts passed to your program.
+    pub fn try_from_account_data(data: &[u8]) -> std::result::Result<PriceUpdateV2, GetPriceError> {
+        // Checks the discriminator and the layout of the data
+        PriceUpdateV2View::try_from_account_data(data)?;
+        PriceUpdateV2::deserialize(&mut &data[PriceUpdateV2::DISCRIMINATOR.len()..])
+            .map_err(|_| GetPriceError::InvalidAccountData)
+    }
+}
+
+/// A maximum age for a price update, measured either in seconds or in slots.
+/// - `Seconds` bounds `clock.unix_timestamp - publish_time`, like the `maximum_age` argument of [`PriceUpdateV2::get_price_no_older_than`].
+/// - `Slots` bounds `clock.slot - posted_slot`, which doesn't depend on the validators' wall clocks.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum MaxAge {
+    Seconds(u64),
+    Slots(u64),
+}
+
+impl MaxAge {
+    pub const fn from_seconds(seconds: u64) -> Self {
+        MaxAge::Seconds(seconds)
+    }
+
+    pub const fn from_slots(slots: u64) -> Self {
+        MaxAge::Slots(slots)
+    }
+}
+
+/// The checks that a price update must pass before its price is returned by [`PriceUpdateV2::get_price_with_checks`] and the other `*_with_checks` getters.
+///
+/// [`PriceChecks::new`] requires a `Full` verification level and a maximum age. The other checks are opt-in and can be chained:
+/// ```
+/// use pyth_solana_receiver_sdk::price_update::{MaxAge, PriceChecks};
+///
+/// const CHECKS: PriceChecks = PriceChecks::new(MaxAge::from_seconds(30))
+///     .with_max_age(MaxAge::from_slots(75))
+///     .with_max_future_skew(5);
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct PriceChecks {
+    maximum_age:         Option<u64>,
+    maximum_slot_age:    Option<u64>,
+    maximum_future_skew: Option<u64>,
+    verification_level:  VerificationLevel,
+}
+
+impl PriceChecks {
+    /// Require a `Full` verification level and a price no older than `max_age`.
+    pub const fn new(max_age: MaxAge) -> Self {
+        PriceChecks {
+            maximum_age:         None,
+            maximum_slot_age:    None,
+            maximum_future_skew: None,
+            verification_level:  VerificationLevel::Full,
+        }
+        .with_max_age(max_age)
+    }
+
+    /// Also bound the age of the price by `max_age`, so that it can be bounded both in seconds and in slots.
+    /// The slot age is `clock.slot - posted_slot`, which doesn't depend on the validators' wall clocks.
+    pub const fn with_max_age(mut self, max_age: MaxAge) -> Self {
+        match max_age {
+            MaxAge::Seconds(seconds) => self.maximum_age = Some(seconds),
+            MaxAge::Slots(slots) => self.maximum_slot_age = Some(slots),
+        }
+        self
+    }
+
+    /// Reject prices whose `publish_time` is more than `maximum_future_skew` seconds ahead of `clock.unix_timestamp`.
+    /// Without it, a `publish_time` in the future passes the maximum age check forever.
+    pub const fn with_max_future_skew(mut self, maximum_future_skew: u64) -> Self {
+        self.maximum_future_skew = Some(maximum_future_skew);
+        self
+    }
+
+    /// Accept price updates verified to at least `verification_level` instead of `Full`.
+    /// Read the [warning](VerificationLevel#warning) about partially verified price updates first.
+    pub const fn with_verification_level(mut self, verification_level: VerificationLevel) -> Self {
+        self.verification_level = verification_level;
+        self
+    }
+
+    pub(crate) fn check_verification_level(
+        &self,
+        verification_level: VerificationLevel,
+    ) -> std::result::Result<(), GetPriceError> {
+        check!(
+            verification_level.gte(self.verification_level),
+            GetPriceError::InsufficientVerificationLevel
+        );
+        Ok(())
+    }
+
+    /// Check the age of `price`, read from a price update posted at `posted_slot`.
+    pub(crate) fn check_price(
+        &self,
+        price: &Price,
+        posted_slot: u64,
+        clock: &Clock,
+    ) -> std::result::Result<(), GetPriceError> {
+        if let Some(maximum_age) = self.maximum_age {
+            check_price_age(price, clock, maximum_age)?;
+        }
+        if let Some(maximum_slot_age) = self.maximum_slot_age {
+            check!(
+                clock.slot.saturating_sub(posted_slot) <= maximum_slot_age,
+                GetPriceError::PriceTooOldInSlots
+            );
+        }
+        if let Some(maximum_future_skew) = self.maximum_future_skew {
+            check_price_future_skew(price, clock, maximum_future_skew)?;
+        }
+        Ok(())
+    }
 }
 
+/// The number of basis points in one, used to express ratios such as a maximum confidence ratio.
+pub const BASIS_POINTS_DENOMINATOR: u64 = 10_000;
+
 /// A Pyth price.
 /// The actual price is `(price ± conf)* 10^exponent`. `publish_time` may be used to check the recency of the price.
 #[derive(PartialEq, Debug, Clone, Copy)]
@@ -83,6 +302,320 @@ pub struct Price {
     pub publish_time: i64,
 }
 
+/// A Pyth price that is known to be strictly positive, so that downstream math can use an unsigned `price`.
+/// The actual price is `(price ± conf)* 10^exponent`, like for a [`Price`].
+///
+/// A `PositivePrice` can only be built from a `Price` whose `price` is greater than zero, see [`Price::to_positive`].
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct PositivePrice {
+    price:        u64,
+    conf:         u64,
+    exponent:     i32,
+    publish_time: i64,
+}
+
+/// How to round when rescaling a [`Price`] loses precision.
+/// - `Down` rounds towards negative infinity.
+/// - `Up` rounds towards positive infinity.
+/// - `Nearest` rounds to the nearest value, with ties away from zero.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Rounding {
+    Down,
+    Up,
+    Nearest,
+}
+
+impl Price {
+    /// Rescale `price` and `conf` so that they are expressed in units of `10^target_exponent`.
+    ///
+    /// Increasing the exponent loses precision, which is handled according to `rounding`. Decreasing it may overflow, in which case `GetPriceError::PriceOverflow` is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use pyth_solana_receiver_sdk::price_update::{Price, Rounding};
+    ///
+    /// let price = Price { price: 14237810000, conf: 7120000, exponent: -8, publish_time: 0 };
+    /// let scaled = price.scale_to_exponent(-2, Rounding::Down).unwrap();
+    /// assert_eq!((scaled.price, scaled.conf, scaled.exponent), (14237, 7, -2));
+    /// ```
+    pub fn scale_to_exponent(
+        &self,
+        target_exponent: i32,
+        rounding: Rounding,
+    ) -> std::result::Result<Price, GetPriceError> {
+        let price = math::rescale(self.price.into(), self.exponent, target_exponent, rounding)
+            .and_then(|price| i64::try_from(price).ok())
+            .ok_or(GetPriceError::PriceOverflow)?;
+        let conf = math::rescale(self.conf.into(), self.exponent, target_exponent, rounding)
+            .and_then(|conf| u64::try_from(conf).ok())
+            .ok_or(GetPriceError::PriceOverflow)?;
+        Ok(Price {
+            price,
+            conf,
+            exponent: target_exponent,
+            publish_time: self.publish_time,
+        })
+    }
+
+    /// Rescale `price` and `conf` so that they have `decimals` decimal places, i.e. an exponent of `-decimals`.
+    pub fn scale_to_decimals(
+        &self,
+        decimals: u8,
+        rounding: Rounding,
+    ) -> std::result::Result<Price, GetPriceError> {
+        self.scale_to_exponent(-i32::from(decimals), rounding)
+    }
+
+    /// Get the price as an unsigned amount with `decimals` decimal places.
+    /// For example, a price of `142.3781` with 6 decimals is `142378100`.
+    ///
+    /// Returns `GetPriceError::NegativePrice` if the price is negative.
+    pub fn to_amount(
+        &self,
+        decimals: u8,
+        rounding: Rounding,
+    ) -> std::result::Result<u64, GetPriceError> {
+        check!(self.price >= 0, GetPriceError::NegativePrice);
+        let scaled = self.scale_to_decimals(decimals, rounding)?;
+        u64::try_from(scaled.price).map_err(|_| GetPriceError::NegativePrice)
+    }
+
+    /// Check that the confidence interval is at most `maximum_confidence_ratio_bps` basis points of the price,
+    /// i.e. that `conf / |price| <= maximum_confidence_ratio_bps / 10_000`.
+    ///
+    /// Returns `GetPriceError::ConfidenceTooWide` otherwise. A zero price only passes if its confidence is also zero.
+    pub fn check_confidence_ratio(
+        &self,
+        maximum_confidence_ratio_bps: u64,
+    ) -> std::result::Result<(), GetPriceError> {
+        check!(
+            u128::from(self.conf) * u128::from(BASIS_POINTS_DENOMINATOR)
+                <= u128::from(maximum_confidence_ratio_bps) * u128::from(self.price.unsigned_abs()),
+            GetPriceError::ConfidenceTooWide
+        );
+        Ok(())
+    }
+
+    /// Get the price of this asset denominated in the `quote` asset, expressed with `result_exponent`.
+    ///
+    /// For example, combining mSOL/USD with a SOL/USD `quote` gives mSOL/SOL.
+    /// The confidence interval is propagated conservatively, as the sum of both relative confidence intervals, and rounded up.
+    /// The resulting `publish_time` is the older of the two `publish_time`s.
+    ///
+    /// Returns `GetPriceError::NonPositivePrice` if the quote price is zero or negative.
+    ///
+    /// # Example
+    /// ```
+    /// use pyth_solana_receiver_sdk::price_update::Price;
+    ///
+    /// let msol_usd = Price { price: 16500000000, conf: 16500000, exponent: -8, publish_time: 100 };
+    /// let sol_usd = Price { price: 15000000000, conf: 15000000, exponent: -8, publish_time: 99 };
+    /// let msol_sol = msol_usd.get_price_in_quote(&sol_usd, -8).unwrap();
+    /// assert_eq!((msol_sol.price, msol_sol.conf, msol_sol.publish_time), (110000000, 220000, 99));
+    /// ```
+    pub fn get_price_in_quote(
+        &self,
+        quote: &Price,
+        result_exponent: i32,
+    ) -> std::result::Result<Price, GetPriceError> {
+        check!(quote.price > 0, GetPriceError::NonPositivePrice);
+        let exponent =
+            i64::from(self.exponent) - i64::from(quote.exponent) - i64::from(result_exponent);
+        let price = math::mul_pow10_div(
+            self.price.into(),
+            exponent,
+            quote.price.into(),
+            Rounding::Nearest,
+        )
+        .and_then(|price| i64::try_from(price).ok())
+        .ok_or(GetPriceError::PriceOverflow)?;
+        // d(a / b) = da / b + |a| * db / b^2 = (da * b + |a| * db) / b^2
+        let conf = i128::from(self.conf)
+            .checked_mul(quote.price.into())
+            .zip(i128::from(self.price.unsigned_abs()).checked_mul(quote.conf.into()))
+            .and_then(|(base_term, quote_term)| base_term.checked_add(quote_term))
+            .and_then(|numerator| {
+                math::mul_pow10_div(
+                    numerator,
+                    exponent,
+                    i128::from(quote.price).pow(2),
+                    Rounding::Up,
+                )
+            })
+            .and_then(|conf| u64::try_from(conf).ok())
+            .ok_or(GetPriceError::PriceOverflow)?;
+        Ok(Price {
+            price,
+            conf,
+            exponent: result_exponent,
+            publish_time: self.publish_time.min(quote.publish_time),
+        })
+    }
+
+    /// Convert this price to a [`PositivePrice`].
+    ///
+    /// Returns `GetPriceError::NonPositivePrice` if the price is zero or negative.
+    pub fn to_positive(&self) -> std::result::Result<PositivePrice, GetPriceError> {
+        PositivePrice::try_from(*self)
+    }
+
+    /// Get the value of `amount` base units of a token with `token_decimals` decimals, in base units of the quote currency with `target_decimals` decimals.
+    /// For example, at a SOL/USD price of `142.3781`, `amount = 2_000_000_000` lamports (9 decimals) are worth `284756200` with 6 target decimals.
+    ///
+    /// The intermediate product `amount * price` is computed exactly in 128 bits, and the only rounding is the final one, in the direction of `rounding`.
+    /// Returns `GetPriceError::NegativePrice` if the price is negative and `GetPriceError::PriceOverflow` if the value doesn't fit in a `u64`.
+    pub fn value_of(
+        &self,
+        amount: u64,
+        token_decimals: u8,
+        target_decimals: u8,
+        rounding: Rounding,
+    ) -> std::result::Result<u64, GetPriceError> {
+        let price = u64::try_from(self.price).map_err(|_| GetPriceError::NegativePrice)?;
+        let exponent =
+            i64::from(self.exponent) + i64::from(target_decimals) - i64::from(token_decimals);
+        math::mul_pow10_div(
+            i128::from(amount) * i128::from(price),
+            exponent,
+            1,
+            rounding,
+        )
+        .and_then(|value| u64::try_from(value).ok())
+        .ok_or(GetPriceError::PriceOverflow)
+    }
+
+    /// Get the amount of base units of a token with `token_decimals` decimals that is worth `value` base units of the quote currency with `value_decimals` decimals.
+    /// This is the inverse of [`Price::value_of`].
+    ///
+    /// The result is rounded in the direction of `rounding`. For example, when computing how many tokens a user must deposit, round up.
+    /// Returns `GetPriceError::NonPositivePrice` if the price is zero or negative and `GetPriceError::PriceOverflow` if the amount doesn't fit in a `u64`.
+    pub fn amount_for_value(
+        &self,
+        value: u64,
+        token_decimals: u8,
+        value_decimals: u8,
+        rounding: Rounding,
+    ) -> std::result::Result<u64, GetPriceError> {
+        check!(self.price > 0, GetPriceError::NonPositivePrice);
+        let exponent =
+            i64::from(token_decimals) - i64::from(self.exponent) - i64::from(value_decimals);
+        math::mul_pow10_div(value.into(), exponent, self.price.into(), rounding)
+            .and_then(|amount| u64::try_from(amount).ok())
+            .ok_or(GetPriceError::PriceOverflow)
+    }
+
+    /// Get the lower bound of the confidence interval, `price - k * conf` where `k = confidence_multiplier_bps / 10_000`.
+    /// For example, a `confidence_multiplier_bps` of `20_000` subtracts two confidence intervals from the price.
+    ///
+    /// This is the conservative price for valuing collateral. The result has a `conf` of zero, is rounded down and saturates at `i64::MIN`.
+    pub fn lower_bound(&self, confidence_multiplier_bps: u64) -> Price {
+        self.with_price(saturate(
+            i128::from(self.price) - self.confidence_margin(confidence_multiplier_bps),
+        ))
+    }
+
+    /// Get the upper bound of the confidence interval, `price + k * conf` where `k = confidence_multiplier_bps / 10_000`.
+    ///
+    /// This is the conservative price for valuing debt. The result has a `conf` of zero, is rounded up and saturates at `i64::MAX`.
+    pub fn upper_bound(&self, confidence_multiplier_bps: u64) -> Price {
+        self.with_price(saturate(
+            i128::from(self.price) + self.confidence_margin(confidence_multiplier_bps),
+        ))
+    }
+
+    /// Like [`Price::lower_bound`], but floored at zero so that a wide confidence interval never values collateral negatively.
+    pub fn non_negative_lower_bound(&self, confidence_multiplier_bps: u64) -> Price {
+        let lower_bound = self.lower_bound(confidence_multiplier_bps);
+        self.with_price(lower_bound.price.max(0))
+    }
+
+    /// Like [`Price::lower_bound`], but returns `GetPriceError::PriceOverflow` instead of saturating.
+    pub fn checked_lower_bound(
+        &self,
+        confidence_multiplier_bps: u64,
+    ) -> std::result::Result<Price, GetPriceError> {
+        i64::try_from(i128::from(self.price) - self.confidence_margin(confidence_multiplier_bps))
+            .map(|price| self.with_price(price))
+            .map_err(|_| GetPriceError::PriceOverflow)
+    }
+
+    /// Like [`Price::upper_bound`], but returns `GetPriceError::PriceOverflow` instead of saturating.
+    pub fn checked_upper_bound(
+        &self,
+        confidence_multiplier_bps: u64,
+    ) -> std::result::Result<Price, GetPriceError> {
+        i64::try_from(i128::from(self.price) + self.confidence_margin(confidence_multiplier_bps))
+            .map(|price| self.with_price(price))
+            .map_err(|_| GetPriceError::PriceOverflow)
+    }
+
+    /// `conf * confidence_multiplier_bps / 10_000`, rounded up so that the bounds are never tighter than requested.
+    fn confidence_margin(&self, confidence_multiplier_bps: u64) -> i128 {
+        let margin = (u128::from(self.conf) * u128::from(confidence_multiplier_bps))
+            .div_ceil(u128::from(BASIS_POINTS_DENOMINATOR));
+        // At most u64::MAX^2 / 10_000 < 2^115
+        margin as i128
+    }
+
+    fn with_price(&self, price: i64) -> Price {
+        Price {
+            price,
+            conf: 0,
+            exponent: self.exponent,
+            publish_time: self.publish_time,
+        }
+    }
+}
+
+impl PositivePrice {
+    /// The price, which is never zero.
+    pub const fn price(&self) -> u64 {
+        self.price
+    }
+
+    pub const fn conf(&self) -> u64 {
+        self.conf
+    }
+
+    pub const fn exponent(&self) -> i32 {
+        self.exponent
+    }
+
+    pub const fn publish_time(&self) -> i64 {
+        self.publish_time
+    }
+}
+
+impl TryFrom<Price> for PositivePrice {
+    type Error = GetPriceError;
+
+    fn try_from(price: Price) -> std::result::Result<Self, Self::Error> {
+        check!(price.price > 0, GetPriceError::NonPositivePrice);
+        Ok(PositivePrice {
+            price:        price.price as u64,
+            conf:         price.conf,
+            exponent:     price.exponent,
+            publish_time: price.publish_time,
+        })
+    }
+}
+
+impl From<PositivePrice> for Price {
+    fn from(price: PositivePrice) -> Self {
+        Price {
+            // A positive i64 is never above i64::MAX
+            price:        price.price as i64,
+            conf:         price.conf,
+            exponent:     price.exponent,
+            publish_time: price.publish_time,
+        }
+    }
+}
+
+fn saturate(price: i128) -> i64 {
+    price.clamp(i64::MIN.into(), i64::MAX.into()) as i64
+}
+
 impl PriceUpdateV2 {
     /// Get a `Price` from a `PriceUpdateV2` account for a given `FeedId`.
     ///
@@ -108,6 +641,110 @@ impl PriceUpdateV2 {
         })
     }
 
+    /// Get the exponentially-weighted moving average (EMA) `Price` from a `PriceUpdateV2` account for a given `FeedId`.
+    ///
+    /// # Warning
+    /// This function does not check :
+    /// - How recent the price is
+    /// - Whether the price update has been verified
+    ///
+    /// It is therefore unsafe to use this function without any extra checks, as it allows for the possibility of using unverified or outdated price updates.
+    pub fn get_ema_price_unchecked(
+        &self,
+        feed_id: &FeedId,
+    ) -> std::result::Result<Price, GetPriceError> {
+        check!(
+            self.price_message.feed_id == *feed_id,
+            GetPriceError::MismatchedFeedId
+        );
+        Ok(Price {
+            price:        self.price_message.ema_price,
+            conf:         self.price_message.ema_conf,
+            exponent:     self.price_message.exponent,
+            publish_time: self.price_message.publish_time,
+        })
+    }
+
+    /// Get a `Price` from a `PriceUpdateV2` account for a given `FeedId`, if the price update passes `checks`.
+    ///
+    /// Checks on the price itself compose with this getter, see for example [`Price::check_confidence_ratio`], [`Price::to_positive`] and [`Price::lower_bound`].
+    ///
+    /// # Example
+    /// ```
+    /// use pyth_solana_receiver_sdk::{error::GetPriceError, feed_id, price_update::{FeedId, MaxAge, Price, PriceChecks, PriceUpdateV2}};
+    /// use solana_program::clock::Clock;
+    ///
+    /// const CHECKS: PriceChecks = PriceChecks::new(MaxAge::from_seconds(30)).with_max_future_skew(5);
+    /// const MAXIMUM_CONFIDENCE_RATIO_BPS : u64 = 200; // 2%
+    /// const CONFIDENCE_MULTIPLIER_BPS : u64 = 20_000; // 2 confidence intervals
+    /// const FEED_ID: FeedId = feed_id!("0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d"); // SOL/USD
+    ///
+    /// pub fn collateral_price(price_update: &PriceUpdateV2, clock: &Clock) -> Result<Price, GetPriceError> {
+    ///     let price = price_update.get_price_with_checks(clock, CHECKS, &FEED_ID)?;
+    ///     price.check_confidence_ratio(MAXIMUM_CONFIDENCE_RATIO_BPS)?;
+    ///     Ok(price.non_negative_lower_bound(CONFIDENCE_MULTIPLIER_BPS))
+    /// }
+    ///```
+    pub fn get_price_with_checks(
+        &self,
+        clock: &Clock,
+        checks: PriceChecks,
+        feed_id: &FeedId,
+    ) -> std::result::Result<Price, GetPriceError> {
+        checks.check_verification_level(self.verification_level)?;
+        let price = self.get_price_unchecked(feed_id)?;
+        checks.check_price(&price, self.posted_slot, clock)?;
+        Ok(price)
+    }
+
+    /// Get the exponentially-weighted moving average (EMA) `Price` from a `PriceUpdateV2` account for a given `FeedId`, if the price update passes `checks`.
+    pub fn get_ema_price_with_checks(
+        &self,
+        clock: &Clock,
+        checks: PriceChecks,
+        feed_id: &FeedId,
+    ) -> std::result::Result<Price, GetPriceError> {
+        checks.check_verification_level(self.verification_level)?;
+        let price = self.get_ema_price_unchecked(feed_id)?;
+        checks.check_price(&price, self.posted_slot, clock)?;
+        Ok(price)
+    }
+
+    /// Get the exponentially-weighted moving average (EMA) `Price` from a `PriceUpdateV2` account for a given `FeedId` no older than `maximum_age` with `Full` verification.
+    ///
+    /// # Example
+    #[cfg_attr(feature = "anchor", doc = "```")]
+    #[cfg_attr(not(feature = "anchor"), doc = "```ignore")]
+    /// use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2};
+    /// use anchor_lang::prelude::*;
+    ///
+    /// const MAXIMUM_AGE : u64 = 30;
+    /// const FEED_ID: &str = "0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d"; // SOL/USD
+    ///
+    /// #[derive(Accounts)]
+    /// pub struct ReadPriceAccount<'info> {
+    ///     pub price_update: Account<'info, PriceUpdateV2>,
+    /// }
+    ///
+    /// pub fn read_price_account(ctx : Context<ReadPriceAccount>) -> Result<()> {
+    ///     let price_update = &mut ctx.accounts.price_update;
+    ///     let ema_price = price_update.get_ema_price_no_older_than(&Clock::get()?, MAXIMUM_AGE, &get_feed_id_from_hex(FEED_ID)?)?;
+    ///     Ok(())
+    /// }
+    ///```
+    pub fn get_ema_price_no_older_than(
+        &self,
+        clock: &Clock,
+        maximum_age: u64,
+        feed_id: &FeedId,
+    ) -> std::result::Result<Price, GetPriceError> {
+        self.get_ema_price_with_checks(
+            clock,
+            PriceChecks::new(MaxAge::Seconds(maximum_age)),
+            feed_id,
+        )
+    }
+
     /// Get a `Price` from a `PriceUpdateV2` account for a given `FeedId` no older than `maximum_age` with customizable verification level.
     ///
     /// # Warning
@@ -115,7 +752,8 @@ impl PriceUpdateV2 {
     /// Please read the documentation for [`VerificationLevel`] for more information.
     ///
     /// # Example
-    /// ```
+    #[cfg_attr(feature = "anchor", doc = "```")]
+    #[cfg_attr(not(feature = "anchor"), doc = "```ignore")]
     /// use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, VerificationLevel, PriceUpdateV2};
     /// use anchor_lang::prelude::*;
     ///
@@ -141,25 +779,19 @@ impl PriceUpdateV2 {
         feed_id: &FeedId,
         verification_level: VerificationLevel,
     ) -> std::result::Result<Price, GetPriceError> {
-        check!(
-            self.verification_level.gte(verification_level),
-            GetPriceError::InsufficientVerificationLevel
-        );
-        let price = self.get_price_unchecked(feed_id)?;
-        check!(
-            price
-                .publish_time
-                .saturating_add(maximum_age.try_into().unwrap())
-                >= clock.unix_timestamp,
-            GetPriceError::PriceTooOld
-        );
-        Ok(price)
+        self.get_price_with_checks(
+            clock,
+            PriceChecks::new(MaxAge::Seconds(maximum_age))
+                .with_verification_level(verification_level),
+            feed_id,
+        )
     }
 
     /// Get a `Price` from a `PriceUpdateV2` account for a given `FeedId` no older than `maximum_age` with `Full` verification.
     ///
     /// # Example
-    /// ```
+    #[cfg_attr(feature = "anchor", doc = "```")]
+    #[cfg_attr(not(feature = "anchor"), doc = "```ignore")]
     /// use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2};
     /// use anchor_lang::prelude::*;
     ///
@@ -193,11 +825,39 @@ impl PriceUpdateV2 {
     }
 }
 
+fn check_price_age(
+    price: &Price,
+    clock: &Clock,
+    maximum_age: u64,
+) -> std::result::Result<(), GetPriceError> {
+    let maximum_age = i64::try_from(maximum_age).map_err(|_| GetPriceError::MaximumAgeTooLarge)?;
+    check!(
+        price.publish_time.saturating_add(maximum_age) >= clock.unix_timestamp,
+        GetPriceError::PriceTooOld
+    );
+    Ok(())
+}
+
+fn check_price_future_skew(
+    price: &Price,
+    clock: &Clock,
+    maximum_future_skew: u64,
+) -> std::result::Result<(), GetPriceError> {
+    check!(
+        i128::from(price.publish_time)
+            <= i128::from(clock.unix_timestamp) + i128::from(maximum_future_skew),
+        GetPriceError::PriceInFuture
+    );
+    Ok(())
+}
+
 /// Get a `FeedId` from a hex string.
 ///
 /// Price feed ids are a 32 byte unique identifier for each price feed in the Pyth network.
 /// They are sometimes represented as a 64 character hex string (with or without a 0x prefix).
 ///
+/// This is equivalent to [`FeedId::from_hex`] and is kept for compatibility. Prefer [`FeedId::from_hex_const`] for feed ids known at compile time.
+///
 /// # Example
 ///
 /// ```
@@ -205,23 +865,9 @@ impl PriceUpdateV2 {
 /// let feed_id = get_feed_id_from_hex("0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d").unwrap();
 /// ```
 pub fn get_feed_id_from_hex(input: &str) -> std::result::Result<FeedId, GetPriceError> {
-    let mut feed_id: FeedId = [0; 32];
-    match input.len() {
-        66 => feed_id.copy_from_slice(
-            &hex::decode(&input[2..]).map_err(|_| GetPriceError::FeedIdNonHexCharacter)?,
-        ),
-        64 => feed_id.copy_from_slice(
-            &hex::decode(input).map_err(|_| GetPriceError::FeedIdNonHexCharacter)?,
-        ),
-        _ => return Err(GetPriceError::FeedIdMustBe32Bytes),
-    }
-    Ok(feed_id)
+    FeedId::from_hex(input)
 }
 
-
-/// Id of a feed producing the message. One feed produces one or more messages.
-pub type FeedId = [u8; 32];
-
 #[repr(C)]
 #[derive(
     Debug,
@@ -235,6 +881,7 @@ pub type FeedId = [u8; 32];
     BorshSchema,
 )]
 pub struct PriceFeedMessage {
+    #[serde(with = "crate::feed_id::bytes")]
     pub feed_id:           FeedId,
     pub price:             i64,
     pub conf:              u64,
@@ -258,6 +905,21 @@ pub struct PriceFeedMessage {
     pub ema_conf:          u64,
 }
 
+impl From<pythnet_sdk::messages::PriceFeedMessage> for PriceFeedMessage {
+    fn from(message: pythnet_sdk::messages::PriceFeedMessage) -> Self {
+        PriceFeedMessage {
+            feed_id:           message.feed_id.into(),
+            price:             message.price,
+            conf:              message.conf,
+            exponent:          message.exponent,
+            publish_time:      message.publish_time,
+            prev_publish_time: message.prev_publish_time,
+            ema_price:         message.ema_price,
+            ema_conf:          message.ema_conf,
+        }
+    }
+}
+
 #[cfg(feature = "quickcheck")]
 impl Arbitrary for PriceFeedMessage {
     fn arbitrary(g: &mut quickcheck::Gen) -> Self {
@@ -269,7 +931,7 @@ impl Arbitrary for PriceFeedMessage {
         let publish_time = i64::arbitrary(g);
 
         PriceFeedMessage {
-            id,
+            feed_id: id.into(),
             price: i64::arbitrary(g),
             conf: u64::arbitrary(g),
             exponent: i32::arbitrary(g),
@@ -279,4 +941,718 @@ impl Arbitrary for PriceFeedMessage {
             ema_conf: u64::arbitrary(g),
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+pub mod tests {
+    use {
+        super::{
+            FeedId,
+            MaxAge,
+            PositivePrice,
+            Price,
+            PriceChecks,
+            PriceFeedMessage,
+            PriceUpdateV2,
+            Rounding,
+            VerificationLevel,
+        },
+        crate::{
+            error::GetPriceError,
+            test_utils::{
+                account_data,
+                price_update,
+            },
+        },
+        pythnet_sdk::wire::{
+            from_slice,
+            to_vec,
+        },
+        solana_program::{
+            account_info::AccountInfo,
+            clock::Clock,
+            pubkey::Pubkey,
+        },
+    };
+
+    fn price(price: i64, conf: u64, exponent: i32) -> Price {
+        Price {
+            price,
+            conf,
+            exponent,
+            publish_time: 0,
+        }
+    }
+
+    fn clock(unix_timestamp: i64) -> Clock {
+        Clock {
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    #[test]
+    fn scale_to_exponent() {
+        let p = price(14237810000, 7120000, -8);
+        assert_eq!(p.scale_to_exponent(-8, Rounding::Down).unwrap(), p);
+        assert_eq!(
+            p.scale_to_exponent(-10, Rounding::Down).unwrap(),
+            price(1423781000000, 712000000, -10)
+        );
+        assert_eq!(
+            p.scale_to_exponent(-2, Rounding::Down).unwrap(),
+            price(14237, 7, -2)
+        );
+        assert_eq!(
+            p.scale_to_exponent(-2, Rounding::Up).unwrap(),
+            price(14238, 8, -2)
+        );
+        assert_eq!(
+            p.scale_to_exponent(-2, Rounding::Nearest).unwrap(),
+            price(14238, 7, -2)
+        );
+        assert_eq!(
+            p.scale_to_exponent(100, Rounding::Up).unwrap(),
+            price(1, 1, 100)
+        );
+        assert_eq!(
+            p.scale_to_exponent(i32::MAX, Rounding::Down).unwrap(),
+            price(0, 0, i32::MAX)
+        );
+    }
+
+    #[test]
+    fn scale_negative_price() {
+        let p = price(-150, 0, -2);
+        assert_eq!(p.scale_to_exponent(0, Rounding::Down).unwrap().price, -2);
+        assert_eq!(p.scale_to_exponent(0, Rounding::Up).unwrap().price, -1);
+        assert_eq!(p.scale_to_exponent(0, Rounding::Nearest).unwrap().price, -2);
+        assert_eq!(
+            price(-149, 0, -2)
+                .scale_to_exponent(0, Rounding::Nearest)
+                .unwrap()
+                .price,
+            -1
+        );
+        assert_eq!(p.scale_to_exponent(50, Rounding::Down).unwrap().price, -1);
+        assert_eq!(p.scale_to_exponent(50, Rounding::Up).unwrap().price, 0);
+    }
+
+    #[test]
+    fn scale_overflow() {
+        assert_eq!(
+            price(i64::MAX, 0, 0).scale_to_exponent(-1, Rounding::Down),
+            Err(GetPriceError::PriceOverflow)
+        );
+        assert_eq!(
+            price(1, u64::MAX, 0).scale_to_exponent(-1, Rounding::Down),
+            Err(GetPriceError::PriceOverflow)
+        );
+        assert_eq!(
+            price(1, 0, i32::MAX).scale_to_exponent(i32::MIN, Rounding::Down),
+            Err(GetPriceError::PriceOverflow)
+        );
+        assert_eq!(
+            price(0, 0, i32::MAX)
+                .scale_to_exponent(i32::MIN, Rounding::Down)
+                .unwrap(),
+            price(0, 0, i32::MIN)
+        );
+    }
+
+    #[test]
+    fn to_amount() {
+        let p = price(14237810000, 7120000, -8);
+        assert_eq!(p.to_amount(6, Rounding::Down).unwrap(), 142378100);
+        assert_eq!(p.to_amount(0, Rounding::Down).unwrap(), 142);
+        assert_eq!(p.to_amount(0, Rounding::Up).unwrap(), 143);
+        assert_eq!(
+            price(-1, 0, -8).to_amount(6, Rounding::Down),
+            Err(GetPriceError::NegativePrice)
+        );
+        // Negative prices that would round to zero are still rejected
+        assert_eq!(
+            price(-1, 0, -8).to_amount(6, Rounding::Up),
+            Err(GetPriceError::NegativePrice)
+        );
+        assert_eq!(
+            price(-1, 0, -8).to_amount(6, Rounding::Nearest),
+            Err(GetPriceError::NegativePrice)
+        );
+        assert_eq!(
+            price(-40, 0, -2).to_amount(0, Rounding::Nearest),
+            Err(GetPriceError::NegativePrice)
+        );
+        assert_eq!(
+            p.to_amount(u8::MAX, Rounding::Down),
+            Err(GetPriceError::PriceOverflow)
+        );
+    }
+
+    #[test]
+    fn value_of() {
+        let sol_usd = price(14237810000, 7120000, -8);
+        assert_eq!(
+            sol_usd.value_of(2_000_000_000, 9, 6, Rounding::Down),
+            Ok(284756200)
+        );
+        assert_eq!(sol_usd.value_of(1, 9, 6, Rounding::Down), Ok(0));
+        assert_eq!(sol_usd.value_of(1, 9, 6, Rounding::Up), Ok(1));
+        assert_eq!(
+            sol_usd.value_of(1, 0, 18, Rounding::Down),
+            Err(GetPriceError::PriceOverflow)
+        );
+        assert_eq!(
+            sol_usd.value_of(u64::MAX, 0, 0, Rounding::Down),
+            Err(GetPriceError::PriceOverflow)
+        );
+        assert_eq!(
+            price(-1, 0, -8).value_of(1, 0, 0, Rounding::Down),
+            Err(GetPriceError::NegativePrice)
+        );
+        assert_eq!(
+            price(0, 0, -8).value_of(u64::MAX, 0, 0, Rounding::Down),
+            Ok(0)
+        );
+    }
+
+    #[test]
+    fn amount_for_value() {
+        let sol_usd = price(14237810000, 7120000, -8);
+        assert_eq!(
+            sol_usd.amount_for_value(284756200, 9, 6, Rounding::Down),
+            Ok(2_000_000_000)
+        );
+        // 1 USD is 0.00702355207... SOL
+        assert_eq!(
+            sol_usd.amount_for_value(1_000_000, 9, 6, Rounding::Down),
+            Ok(7023552)
+        );
+        assert_eq!(
+            sol_usd.amount_for_value(1_000_000, 9, 6, Rounding::Up),
+            Ok(7023553)
+        );
+        assert_eq!(
+            price(0, 0, -8).amount_for_value(1, 9, 6, Rounding::Down),
+            Err(GetPriceError::NonPositivePrice)
+        );
+        assert_eq!(
+            price(1, 0, -8).amount_for_value(u64::MAX, 9, 6, Rounding::Down),
+            Err(GetPriceError::PriceOverflow)
+        );
+    }
+
+    #[test]
+    fn get_price_in_quote() {
+        let base = Price {
+            publish_time: 100,
+            ..price(16500000000, 16500000, -8)
+        };
+        let quote = Price {
+            publish_time: 99,
+            ..price(15000000000, 15000000, -8)
+        };
+        assert_eq!(
+            base.get_price_in_quote(&quote, -8).unwrap(),
+            Price {
+                publish_time: 99,
+                ..price(110000000, 220000, -8)
+            }
+        );
+        assert_eq!(
+            base.get_price_in_quote(&quote, -4).unwrap(),
+            Price {
+                publish_time: 99,
+                ..price(11000, 22, -4)
+            }
+        );
+
+        // Quote with a different exponent
+        let quote = price(150, 1, 0);
+        assert_eq!(
+            base.get_price_in_quote(&quote, -8).unwrap().price,
+            110000000
+        );
+        // 0.001 + 1/150 relative confidence, rounded up
+        assert_eq!(base.get_price_in_quote(&quote, -8).unwrap().conf, 843334);
+
+        assert_eq!(
+            price(-16500000000, 16500000, -8)
+                .get_price_in_quote(&quote, -8)
+                .unwrap(),
+            price(-110000000, 843334, -8)
+        );
+        assert_eq!(
+            price(0, 0, -8).get_price_in_quote(&quote, -8).unwrap(),
+            price(0, 0, -8)
+        );
+    }
+
+    #[test]
+    fn get_price_in_quote_errors() {
+        let base = price(16500000000, 16500000, -8);
+        assert_eq!(
+            base.get_price_in_quote(&price(0, 0, -8), -8),
+            Err(GetPriceError::NonPositivePrice)
+        );
+        assert_eq!(
+            base.get_price_in_quote(&price(-1, 0, -8), -8),
+            Err(GetPriceError::NonPositivePrice)
+        );
+        assert_eq!(
+            base.get_price_in_quote(&price(1, 0, 0), -30),
+            Err(GetPriceError::PriceOverflow)
+        );
+    }
+
+    #[test]
+    fn get_ema_price() {
+        let feed_id = FeedId::new([1; 32]);
+        let mut update = price_update(feed_id, 16500000000, 16500000, 100);
+        update.price_message.ema_price = 16400000000;
+        update.price_message.ema_conf = 20000000;
+
+        let ema_price = Price {
+            publish_time: 100,
+            ..price(16400000000, 20000000, -8)
+        };
+        assert_eq!(update.get_ema_price_unchecked(&feed_id).unwrap(), ema_price);
+        assert_eq!(
+            update
+                .get_ema_price_no_older_than(&clock(110), 10, &feed_id)
+                .unwrap(),
+            ema_price
+        );
+        assert_eq!(
+            update.get_ema_price_no_older_than(&clock(111), 10, &feed_id),
+            Err(GetPriceError::PriceTooOld)
+        );
+        assert_eq!(
+            update.get_ema_price_unchecked(&FeedId::new([2; 32])),
+            Err(GetPriceError::MismatchedFeedId)
+        );
+
+        update.verification_level = VerificationLevel::Partial { num_signatures: 5 };
+        assert_eq!(
+            update.get_ema_price_no_older_than(&clock(110), 10, &feed_id),
+            Err(GetPriceError::InsufficientVerificationLevel)
+        );
+        assert_eq!(
+            update
+                .get_ema_price_with_checks(
+                    &clock(110),
+                    PriceChecks::new(MaxAge::from_seconds(10))
+                        .with_verification_level(VerificationLevel::Partial { num_signatures: 5 }),
+                    &feed_id
+                )
+                .unwrap(),
+            ema_price
+        );
+    }
+
+    #[test]
+    fn check_confidence_ratio() {
+        assert_eq!(price(10000, 100, -8).check_confidence_ratio(100), Ok(()));
+        assert_eq!(
+            price(10000, 101, -8).check_confidence_ratio(100),
+            Err(GetPriceError::ConfidenceTooWide)
+        );
+        assert_eq!(price(-10000, 100, -8).check_confidence_ratio(100), Ok(()));
+        assert_eq!(price(0, 0, -8).check_confidence_ratio(0), Ok(()));
+        assert_eq!(
+            price(0, 1, -8).check_confidence_ratio(u64::MAX),
+            Err(GetPriceError::ConfidenceTooWide)
+        );
+        assert_eq!(
+            price(i64::MAX, u64::MAX, -8).check_confidence_ratio(u64::MAX),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn get_price_with_checks() {
+        let feed_id = FeedId::new([1; 32]);
+        let mut update = price_update(feed_id, 15000000000, 300000000, 100);
+        update.posted_slot = 1000;
+        let clock = Clock {
+            slot: 1075,
+            ..clock(100)
+        };
+
+        let checks =
+            PriceChecks::new(MaxAge::from_seconds(10)).with_max_age(MaxAge::from_slots(75));
+        assert_eq!(
+            update
+                .get_price_with_checks(&clock, checks, &feed_id)
+                .unwrap()
+                .price,
+            15000000000
+        );
+        assert_eq!(
+            update.get_price_with_checks(
+                &clock,
+                checks.with_max_age(MaxAge::from_slots(74)),
+                &feed_id
+            ),
+            Err(GetPriceError::PriceTooOldInSlots)
+        );
+        assert_eq!(
+            update.get_price_with_checks(
+                &Clock {
+                    unix_timestamp: 111,
+                    ..clock.clone()
+                },
+                checks,
+                &feed_id
+            ),
+            Err(GetPriceError::PriceTooOld)
+        );
+        assert_eq!(
+            update.get_price_with_checks(
+                &clock,
+                PriceChecks::new(MaxAge::from_slots(75)),
+                &feed_id
+            ),
+            update.get_price_unchecked(&feed_id)
+        );
+        assert_eq!(
+            update.get_price_with_checks(
+                &clock,
+                PriceChecks::new(MaxAge::from_seconds(u64::MAX)),
+                &feed_id
+            ),
+            Err(GetPriceError::MaximumAgeTooLarge)
+        );
+        assert_eq!(
+            update.get_price_with_checks(&clock, checks, &FeedId::new([2; 32])),
+            Err(GetPriceError::MismatchedFeedId)
+        );
+
+        update.verification_level = VerificationLevel::Partial { num_signatures: 5 };
+        assert_eq!(
+            update.get_price_with_checks(&clock, checks, &feed_id),
+            Err(GetPriceError::InsufficientVerificationLevel)
+        );
+        assert!(update
+            .get_price_with_checks(
+                &clock,
+                checks.with_verification_level(VerificationLevel::Partial { num_signatures: 5 }),
+                &feed_id
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn max_future_skew() {
+        let feed_id = FeedId::new([1; 32]);
+        let update = price_update(feed_id, 15000000000, 300000000, 105);
+        let checks = PriceChecks::new(MaxAge::from_seconds(10));
+
+        assert_eq!(
+            update
+                .get_price_with_checks(&clock(100), checks.with_max_future_skew(5), &feed_id)
+                .unwrap()
+                .publish_time,
+            105
+        );
+        assert_eq!(
+            update.get_price_with_checks(&clock(100), checks.with_max_future_skew(4), &feed_id),
+            Err(GetPriceError::PriceInFuture)
+        );
+        assert_eq!(
+            update.get_price_with_checks(&clock(116), checks.with_max_future_skew(0), &feed_id),
+            Err(GetPriceError::PriceTooOld)
+        );
+        assert_eq!(
+            price_update(feed_id, 1, 0, i64::MAX)
+                .get_price_with_checks(
+                    &clock(i64::MAX),
+                    PriceChecks::new(MaxAge::from_seconds(0)).with_max_future_skew(u64::MAX),
+                    &feed_id
+                )
+                .unwrap()
+                .publish_time,
+            i64::MAX
+        );
+        // Without a maximum future skew, a price in the future is accepted
+        assert!(update
+            .get_price_no_older_than(&clock(0), 0, &feed_id)
+            .is_ok());
+    }
+
+    #[test]
+    fn maximum_age_too_large() {
+        let feed_id = FeedId::new([1; 32]);
+        let update = price_update(feed_id, 15000000000, 300000000, 100);
+        assert_eq!(
+            update.get_price_no_older_than(&clock(100), u64::MAX, &feed_id),
+            Err(GetPriceError::MaximumAgeTooLarge)
+        );
+        assert_eq!(
+            update
+                .get_price_no_older_than(&clock(i64::MAX), i64::MAX as u64, &feed_id)
+                .unwrap()
+                .publish_time,
+            100
+        );
+        assert_eq!(
+            update
+                .get_price_no_older_than(&clock(i64::MIN), 0, &feed_id)
+                .unwrap()
+                .publish_time,
+            100
+        );
+    }
+
+    #[cfg(feature = "anchor")]
+    #[test]
+    fn anchor_discriminator() {
+        use anchor_lang::{
+            AccountSerialize,
+            Discriminator,
+        };
+
+        assert_eq!(
+            PriceUpdateV2::DISCRIMINATOR,
+            <PriceUpdateV2 as Discriminator>::DISCRIMINATOR
+        );
+        let update = price_update(FeedId::new([1; 32]), 100, 1, 10);
+        let mut data = vec![];
+        update.try_serialize(&mut data).unwrap();
+        data.resize(PriceUpdateV2::LEN, 0);
+        assert_eq!(data, account_data(&update));
+    }
+
+    #[test]
+    fn try_from_account_data() {
+        let feed_id = FeedId::new([1; 32]);
+        let mut update = price_update(feed_id, 15000000000, 300000000, 100);
+        update.posted_slot = 1000;
+
+        let data = account_data(&update);
+        let decoded = PriceUpdateV2::try_from_account_data(&data).unwrap();
+        assert_eq!(decoded.write_authority, update.write_authority);
+        assert_eq!(decoded.verification_level, update.verification_level);
+        assert_eq!(decoded.price_message, update.price_message);
+        assert_eq!(decoded.posted_slot, update.posted_slot);
+        // `Full` updates don't need the padding byte
+        assert!(PriceUpdateV2::try_from_account_data(&data[..PriceUpdateV2::LEN - 1]).is_ok());
+
+        update.verification_level = VerificationLevel::Partial { num_signatures: 5 };
+        let data = account_data(&update);
+        assert_eq!(
+            PriceUpdateV2::try_from_account_data(&data)
+                .unwrap()
+                .verification_level,
+            update.verification_level
+        );
+        assert_eq!(
+            PriceUpdateV2::try_from_account_data(&data[..PriceUpdateV2::LEN - 1]).err(),
+            Some(GetPriceError::AccountDataTooShort)
+        );
+    }
+
+    #[test]
+    fn try_from_account_data_errors() {
+        let data = account_data(&price_update(
+            FeedId::new([1; 32]),
+            15000000000,
+            300000000,
+            100,
+        ));
+
+        assert_eq!(
+            PriceUpdateV2::try_from_account_data(&data[..7]).err(),
+            Some(GetPriceError::AccountDataTooShort)
+        );
+        assert_eq!(
+            PriceUpdateV2::try_from_account_data(&data[..50]).err(),
+            Some(GetPriceError::AccountDataTooShort)
+        );
+
+        let mut wrong_discriminator = data.clone();
+        wrong_discriminator[0] ^= 1;
+        assert_eq!(
+            PriceUpdateV2::try_from_account_data(&wrong_discriminator).err(),
+            Some(GetPriceError::WrongAccountDiscriminator)
+        );
+
+        let mut wrong_verification_level = data;
+        wrong_verification_level[8 + 32] = 2;
+        assert_eq!(
+            PriceUpdateV2::try_from_account_data(&wrong_verification_level).err(),
+            Some(GetPriceError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn try_from_account_info() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = account_data(&price_update(
+            FeedId::new([1; 32]),
+            15000000000,
+            300000000,
+            100,
+        ));
+
+        let owner = crate::ID;
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        assert!(PriceUpdateV2::try_from_account_info(&account_info).is_ok());
+
+        let owner = Pubkey::new_unique();
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        assert_eq!(
+            PriceUpdateV2::try_from_account_info(&account_info).err(),
+            Some(GetPriceError::WrongAccountOwner)
+        );
+    }
+
+    #[test]
+    fn verification_level_ordering() {
+        let levels = [
+            VerificationLevel::Partial { num_signatures: 0 },
+            VerificationLevel::Partial { num_signatures: 5 },
+            VerificationLevel::Partial { num_signatures: 13 },
+            VerificationLevel::Full,
+        ];
+        for a in levels {
+            for b in levels {
+                assert_eq!(a.gte(b), a >= b);
+            }
+        }
+        let mut sorted = levels;
+        sorted.reverse();
+        sorted.sort();
+        assert_eq!(sorted, levels);
+        assert_eq!(levels.iter().max(), Some(&VerificationLevel::Full));
+    }
+
+    #[test]
+    fn verification_level_from_signatures() {
+        assert_eq!(VerificationLevel::quorum(19), 13);
+        assert_eq!(VerificationLevel::quorum(1), 1);
+        assert_eq!(VerificationLevel::quorum(u8::MAX), 171);
+        assert_eq!(
+            VerificationLevel::from_signatures(12, 19),
+            VerificationLevel::Partial { num_signatures: 12 }
+        );
+        assert_eq!(
+            VerificationLevel::from_signatures(13, 19),
+            VerificationLevel::Full
+        );
+
+        assert!(VerificationLevel::Full.meets_minimum_signatures(u8::MAX));
+        assert!(VerificationLevel::Partial { num_signatures: 5 }.meets_minimum_signatures(5));
+        assert!(!VerificationLevel::Partial { num_signatures: 4 }.meets_minimum_signatures(5));
+    }
+
+    #[test]
+    fn confidence_bounds() {
+        let p = Price {
+            publish_time: 100,
+            ..price(10000, 101, -8)
+        };
+        let bounded = |price| Price {
+            price,
+            conf: 0,
+            exponent: -8,
+            publish_time: 100,
+        };
+        assert_eq!(p.lower_bound(10_000), bounded(9899));
+        assert_eq!(p.upper_bound(10_000), bounded(10101));
+        // 1.5 * 101 = 151.5 is rounded up
+        assert_eq!(p.lower_bound(15_000), bounded(9848));
+        assert_eq!(p.upper_bound(15_000), bounded(10152));
+        assert_eq!(p.lower_bound(0), bounded(10000));
+        assert_eq!(p.non_negative_lower_bound(10_000), bounded(9899));
+        assert_eq!(p.non_negative_lower_bound(1_000_000), bounded(0));
+        assert_eq!(p.checked_lower_bound(1_000_000), Ok(bounded(-100)));
+
+        let wide = Price {
+            publish_time: 100,
+            ..price(0, u64::MAX, -8)
+        };
+        assert_eq!(wide.lower_bound(u64::MAX), bounded(i64::MIN));
+        assert_eq!(wide.upper_bound(u64::MAX), bounded(i64::MAX));
+        assert_eq!(
+            wide.checked_lower_bound(10_000),
+            Err(GetPriceError::PriceOverflow)
+        );
+        assert_eq!(
+            wide.checked_upper_bound(10_000),
+            Err(GetPriceError::PriceOverflow)
+        );
+    }
+
+    #[test]
+    fn positive_price() {
+        let p = Price {
+            publish_time: 100,
+            ..price(10000, 101, -8)
+        };
+        let positive = p.to_positive().unwrap();
+        assert_eq!(positive.price(), 10000);
+        assert_eq!(positive.conf(), 101);
+        assert_eq!(positive.exponent(), -8);
+        assert_eq!(positive.publish_time(), 100);
+        assert_eq!(Price::from(positive), p);
+        assert_eq!(
+            PositivePrice::try_from(price(i64::MAX, 0, 0))
+                .unwrap()
+                .price(),
+            i64::MAX as u64
+        );
+
+        assert_eq!(
+            price(0, 0, -8).to_positive(),
+            Err(GetPriceError::NonPositivePrice)
+        );
+        assert_eq!(
+            price(-1, 0, -8).to_positive(),
+            Err(GetPriceError::NonPositivePrice)
+        );
+    }
+
+    #[test]
+    fn price_feed_message_wire_format() {
+        let message = pythnet_sdk::messages::PriceFeedMessage {
+            feed_id:           [3; 32],
+            price:             14237810000,
+            conf:              7120000,
+            exponent:          -8,
+            publish_time:      100,
+            prev_publish_time: 99,
+            ema_price:         14237000000,
+            ema_conf:          7000000,
+        };
+        let bytes = to_vec::<_, byteorder::BE>(&message).unwrap();
+        assert_eq!(bytes.len(), 32 + 8 + 8 + 4 + 8 + 8 + 8 + 8);
+
+        assert_eq!(
+            to_vec::<_, byteorder::BE>(&PriceFeedMessage::from(message)).unwrap(),
+            bytes
+        );
+        assert_eq!(
+            from_slice::<byteorder::BE, PriceFeedMessage>(&bytes).unwrap(),
+            PriceFeedMessage::from(message)
+        );
+    }
+}
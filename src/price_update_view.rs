@@ -0,0 +1,376 @@
+use {
+    crate::{
+        check,
+        error::GetPriceError,
+        price_update::{
+            FeedId,
+            MaxAge,
+            Price,
+            PriceChecks,
+            PriceUpdateV2,
+            VerificationLevel,
+        },
+    },
+    solana_program::{
+        clock::Clock,
+        pubkey::Pubkey,
+    },
+};
+
+const DISCRIMINATOR_LEN: usize = 8;
+const WRITE_AUTHORITY_OFFSET: usize = DISCRIMINATOR_LEN;
+const VERIFICATION_LEVEL_OFFSET: usize = WRITE_AUTHORITY_OFFSET + 32;
+
+// Offsets relative to the start of the price message
+const FEED_ID_OFFSET: usize = 0;
+const PRICE_OFFSET: usize = FEED_ID_OFFSET + 32;
+const CONF_OFFSET: usize = PRICE_OFFSET + 8;
+const EXPONENT_OFFSET: usize = CONF_OFFSET + 8;
+const PUBLISH_TIME_OFFSET: usize = EXPONENT_OFFSET + 4;
+const PREV_PUBLISH_TIME_OFFSET: usize = PUBLISH_TIME_OFFSET + 8;
+const EMA_PRICE_OFFSET: usize = PREV_PUBLISH_TIME_OFFSET + 8;
+const EMA_CONF_OFFSET: usize = EMA_PRICE_OFFSET + 8;
+const POSTED_SLOT_OFFSET: usize = EMA_CONF_OFFSET + 8;
+
+/// A read-only view over the data of a [`PriceUpdateV2`] account.
+///
+/// Unlike deserializing a `PriceUpdateV2`, creating a view doesn't copy the account data: each accessor reads its field directly from the account bytes.
+/// This saves compute units when a program only needs a few fields from many price update accounts.
+///
+/// # Warning
+/// A view doesn't check the owner of the account. Check that `account_info.owner` is the Pyth Solana Receiver program before reading its data.
+///
+/// # Example
+/// ```
+/// use pyth_solana_receiver_sdk::{error::GetPriceError, price_update::get_feed_id_from_hex, price_update_view::PriceUpdateV2View};
+/// use solana_program::{account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, sysvar::Sysvar};
+///
+/// const MAXIMUM_AGE : u64 = 30;
+/// const FEED_ID: &str = "0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d"; // SOL/USD
+///
+/// pub fn read_price_account(price_update_account: &AccountInfo) -> ProgramResult {
+///     if *price_update_account.owner != pyth_solana_receiver_sdk::ID {
+///         return Err(GetPriceError::WrongAccountOwner.into());
+///     }
+///     let data = price_update_account.try_borrow_data()?;
+///     let price_update = PriceUpdateV2View::try_from_account_data(&data)?;
+///     let price = price_update.get_price_no_older_than(&Clock::get()?, MAXIMUM_AGE, &get_feed_id_from_hex(FEED_ID)?)?;
+///     Ok(())
+/// }
+///```
+#[derive(Debug, Clone, Copy)]
+pub struct PriceUpdateV2View<'a> {
+    data:                 &'a [u8],
+    price_message_offset: usize,
+}
+
+impl<'a> PriceUpdateV2View<'a> {
+    /// Create a view over the data of a `PriceUpdateV2` account, checking its discriminator and that it is long enough for all the fields.
+    pub fn try_from_account_data(data: &'a [u8]) -> std::result::Result<Self, GetPriceError> {
+        check!(
+            data.len() >= DISCRIMINATOR_LEN,
+            GetPriceError::AccountDataTooShort
+        );
+        check!(
+            data[..DISCRIMINATOR_LEN] == PriceUpdateV2::DISCRIMINATOR,
+            GetPriceError::WrongAccountDiscriminator
+        );
+        // `Partial` is serialized as its tag followed by `num_signatures`, `Full` as its tag only
+        let price_message_offset = match data.get(VERIFICATION_LEVEL_OFFSET) {
+            Some(0) => VERIFICATION_LEVEL_OFFSET + 2,
+            Some(1) => VERIFICATION_LEVEL_OFFSET + 1,
+            Some(_) => return Err(GetPriceError::InvalidAccountData),
+            None => return Err(GetPriceError::AccountDataTooShort),
+        };
+        check!(
+            data.len() >= price_message_offset + POSTED_SLOT_OFFSET + 8,
+            GetPriceError::AccountDataTooShort
+        );
+        Ok(PriceUpdateV2View {
+            data,
+            price_message_offset,
+        })
+    }
+
+    pub fn write_authority(&self) -> Pubkey {
+        Pubkey::new_from_array(self.read(WRITE_AUTHORITY_OFFSET))
+    }
+
+    pub fn verification_level(&self) -> VerificationLevel {
+        match self.data[VERIFICATION_LEVEL_OFFSET] {
+            0 => VerificationLevel::Partial {
+                num_signatures: self.data[VERIFICATION_LEVEL_OFFSET + 1],
+            },
+            _ => VerificationLevel::Full,
+        }
+    }
+
+    pub fn feed_id(&self) -> FeedId {
+        FeedId::new(self.read_price_message(FEED_ID_OFFSET))
+    }
+
+    pub fn price(&self) -> i64 {
+        i64::from_le_bytes(self.read_price_message(PRICE_OFFSET))
+    }
+
+    pub fn conf(&self) -> u64 {
+        u64::from_le_bytes(self.read_price_message(CONF_OFFSET))
+    }
+
+    pub fn exponent(&self) -> i32 {
+        i32::from_le_bytes(self.read_price_message(EXPONENT_OFFSET))
+    }
+
+    pub fn publish_time(&self) -> i64 {
+        i64::from_le_bytes(self.read_price_message(PUBLISH_TIME_OFFSET))
+    }
+
+    pub fn prev_publish_time(&self) -> i64 {
+        i64::from_le_bytes(self.read_price_message(PREV_PUBLISH_TIME_OFFSET))
+    }
+
+    pub fn ema_price(&self) -> i64 {
+        i64::from_le_bytes(self.read_price_message(EMA_PRICE_OFFSET))
+    }
+
+    pub fn ema_conf(&self) -> u64 {
+        u64::from_le_bytes(self.read_price_message(EMA_CONF_OFFSET))
+    }
+
+    pub fn posted_slot(&self) -> u64 {
+        u64::from_le_bytes(self.read_price_message(POSTED_SLOT_OFFSET))
+    }
+
+    /// Get a `Price` for a given `FeedId`, like [`PriceUpdateV2::get_price_unchecked`].
+    ///
+    /// # Warning
+    /// Like [`PriceUpdateV2::get_price_unchecked`], this function checks neither the age nor the [verification level](VerificationLevel#warning) of the price update.
+    pub fn get_price_unchecked(
+        &self,
+        feed_id: &FeedId,
+    ) -> std::result::Result<Price, GetPriceError> {
+        check!(self.feed_id() == *feed_id, GetPriceError::MismatchedFeedId);
+        Ok(Price {
+            price:        self.price(),
+            conf:         self.conf(),
+            exponent:     self.exponent(),
+            publish_time: self.publish_time(),
+        })
+    }
+
+    /// Get a `Price` for a given `FeedId` if the price update passes `checks`, like [`PriceUpdateV2::get_price_with_checks`].
+    pub fn get_price_with_checks(
+        &self,
+        clock: &Clock,
+        checks: PriceChecks,
+        feed_id: &FeedId,
+    ) -> std::result::Result<Price, GetPriceError> {
+        checks.check_verification_level(self.verification_level())?;
+        let price = self.get_price_unchecked(feed_id)?;
+        checks.check_price(&price, self.posted_slot(), clock)?;
+        Ok(price)
+    }
+
+    /// Get a `Price` for a given `FeedId` no older than `maximum_age` with `Full` verification, like [`PriceUpdateV2::get_price_no_older_than`].
+    pub fn get_price_no_older_than(
+        &self,
+        clock: &Clock,
+        maximum_age: u64,
+        feed_id: &FeedId,
+    ) -> std::result::Result<Price, GetPriceError> {
+        self.get_price_with_checks(
+            clock,
+            PriceChecks::new(MaxAge::Seconds(maximum_age)),
+            feed_id,
+        )
+    }
+
+    fn read<const N: usize>(&self, offset: usize) -> [u8; N] {
+        let mut bytes = [0; N];
+        bytes.copy_from_slice(&self.data[offset..offset + N]);
+        bytes
+    }
+
+    fn read_price_message<const N: usize>(&self, offset: usize) -> [u8; N] {
+        self.read(self.price_message_offset + offset)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use {
+        super::{
+            PriceUpdateV2View,
+            DISCRIMINATOR_LEN,
+            VERIFICATION_LEVEL_OFFSET,
+        },
+        crate::{
+            error::GetPriceError,
+            price_update::{
+                FeedId,
+                MaxAge,
+                PriceChecks,
+                PriceFeedMessage,
+                PriceUpdateV2,
+                VerificationLevel,
+            },
+            test_utils,
+        },
+        solana_program::{
+            clock::Clock,
+            pubkey::Pubkey,
+        },
+    };
+
+    const FEED_ID: FeedId = FeedId::new([7; 32]);
+
+    fn account_data(
+        verification_level: VerificationLevel,
+        price_message: PriceFeedMessage,
+    ) -> Vec<u8> {
+        test_utils::account_data(&PriceUpdateV2 {
+            write_authority: Pubkey::new_unique(),
+            verification_level,
+            price_message,
+            posted_slot: 123456789,
+        })
+    }
+
+    fn price_message(price: i64, conf: u64, exponent: i32, publish_time: i64) -> PriceFeedMessage {
+        PriceFeedMessage {
+            feed_id: FEED_ID,
+            price,
+            conf,
+            exponent,
+            publish_time,
+            prev_publish_time: publish_time - 1,
+            ema_price: price / 2,
+            ema_conf: conf * 2,
+        }
+    }
+
+    fn assert_decodes_like_borsh(data: &[u8]) {
+        let view = PriceUpdateV2View::try_from_account_data(data).unwrap();
+        let price_update = PriceUpdateV2::try_from_account_data(data).unwrap();
+
+        assert_eq!(view.write_authority(), price_update.write_authority);
+        assert_eq!(view.verification_level(), price_update.verification_level);
+        assert_eq!(view.feed_id(), price_update.price_message.feed_id);
+        assert_eq!(view.price(), price_update.price_message.price);
+        assert_eq!(view.conf(), price_update.price_message.conf);
+        assert_eq!(view.exponent(), price_update.price_message.exponent);
+        assert_eq!(view.publish_time(), price_update.price_message.publish_time);
+        assert_eq!(
+            view.prev_publish_time(),
+            price_update.price_message.prev_publish_time
+        );
+        assert_eq!(view.ema_price(), price_update.price_message.ema_price);
+        assert_eq!(view.ema_conf(), price_update.price_message.ema_conf);
+        assert_eq!(view.posted_slot(), price_update.posted_slot);
+        assert_eq!(
+            view.get_price_unchecked(&FEED_ID),
+            price_update.get_price_unchecked(&FEED_ID)
+        );
+    }
+
+    #[test]
+    fn decodes_like_borsh() {
+        for verification_level in [
+            VerificationLevel::Full,
+            VerificationLevel::Partial { num_signatures: 0 },
+            VerificationLevel::Partial { num_signatures: 13 },
+        ] {
+            for message in [
+                price_message(15000000000, 300000000, -8, 1700000000),
+                price_message(-42, 0, 5, -1),
+                price_message(i64::MAX, u64::MAX / 2, i32::MIN, i64::MAX),
+                price_message(i64::MIN, 0, i32::MAX, i64::MIN + 1),
+            ] {
+                assert_decodes_like_borsh(&account_data(verification_level, message));
+            }
+        }
+    }
+
+    #[test]
+    fn try_from_account_data_errors() {
+        let data = account_data(
+            VerificationLevel::Partial { num_signatures: 5 },
+            price_message(15000000000, 300000000, -8, 1700000000),
+        );
+        assert!(PriceUpdateV2View::try_from_account_data(&data).is_ok());
+
+        assert_eq!(
+            PriceUpdateV2View::try_from_account_data(&data[..DISCRIMINATOR_LEN - 1]).err(),
+            Some(GetPriceError::AccountDataTooShort)
+        );
+        assert_eq!(
+            PriceUpdateV2View::try_from_account_data(&data[..VERIFICATION_LEVEL_OFFSET]).err(),
+            Some(GetPriceError::AccountDataTooShort)
+        );
+        assert_eq!(
+            PriceUpdateV2View::try_from_account_data(&data[..PriceUpdateV2::LEN - 1]).err(),
+            Some(GetPriceError::AccountDataTooShort)
+        );
+
+        let mut wrong_discriminator = data.clone();
+        wrong_discriminator[7] ^= 1;
+        assert_eq!(
+            PriceUpdateV2View::try_from_account_data(&wrong_discriminator).err(),
+            Some(GetPriceError::WrongAccountDiscriminator)
+        );
+
+        let mut wrong_verification_level = data;
+        wrong_verification_level[VERIFICATION_LEVEL_OFFSET] = 2;
+        assert_eq!(
+            PriceUpdateV2View::try_from_account_data(&wrong_verification_level).err(),
+            Some(GetPriceError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn get_price_with_checks() {
+        let data = account_data(
+            VerificationLevel::Partial { num_signatures: 5 },
+            price_message(15000000000, 300000000, -8, 100),
+        );
+        let view = PriceUpdateV2View::try_from_account_data(&data).unwrap();
+        let clock = Clock {
+            slot: 123456799,
+            unix_timestamp: 110,
+            ..Clock::default()
+        };
+        let partial = PriceChecks::new(MaxAge::from_seconds(10))
+            .with_verification_level(VerificationLevel::Partial { num_signatures: 5 });
+
+        assert_eq!(
+            view.get_price_no_older_than(&clock, 10, &FEED_ID),
+            Err(GetPriceError::InsufficientVerificationLevel)
+        );
+        assert_eq!(
+            view.get_price_with_checks(
+                &clock,
+                partial.with_max_age(MaxAge::from_seconds(9)),
+                &FEED_ID
+            ),
+            Err(GetPriceError::PriceTooOld)
+        );
+        assert_eq!(
+            view.get_price_with_checks(
+                &clock,
+                partial.with_max_age(MaxAge::from_slots(9)),
+                &FEED_ID
+            ),
+            Err(GetPriceError::PriceTooOldInSlots)
+        );
+        assert_eq!(
+            view.get_price_with_checks(
+                &clock,
+                partial.with_max_age(MaxAge::from_slots(10)),
+                &FEED_ID
+            )
+            .unwrap()
+            .price,
+            15000000000
+        );
+    }
+}
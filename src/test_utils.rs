@@ -0,0 +1,44 @@
+//! Fixtures shared by the unit tests of this crate.
+
+use {
+    crate::price_update::{
+        FeedId,
+        PriceFeedMessage,
+        PriceUpdateV2,
+        VerificationLevel,
+    },
+    borsh::BorshSerialize,
+    solana_program::pubkey::Pubkey,
+};
+
+/// A fully verified `PriceUpdateV2` with exponent `-8` whose EMA fields mirror the spot ones.
+pub(crate) fn price_update(
+    feed_id: FeedId,
+    price: i64,
+    conf: u64,
+    publish_time: i64,
+) -> PriceUpdateV2 {
+    PriceUpdateV2 {
+        write_authority:    Pubkey::new_unique(),
+        verification_level: VerificationLevel::Full,
+        price_message:      PriceFeedMessage {
+            feed_id,
+            price,
+            conf,
+            exponent: -8,
+            publish_time,
+            prev_publish_time: publish_time - 1,
+            ema_price: price,
+            ema_conf: conf,
+        },
+        posted_slot:        0,
+    }
+}
+
+/// The data of a `PriceUpdateV2` account holding `price_update`, padded to `PriceUpdateV2::LEN`.
+pub(crate) fn account_data(price_update: &PriceUpdateV2) -> Vec<u8> {
+    let mut data = PriceUpdateV2::DISCRIMINATOR.to_vec();
+    data.extend(price_update.try_to_vec().unwrap());
+    data.resize(PriceUpdateV2::LEN, 0);
+    data
+}